@@ -0,0 +1,98 @@
+use crate::{parse_amount, reconnecting_rpc, timeout, utils, Saturn, SaturnError};
+use wasm_bindgen::prelude::*;
+
+/// Options for connecting a `Saturn` client, so new options don't change the
+/// constructor's signature.
+///
+/// ```js
+/// const saturn = await new SaturnBuilder()
+///   .url("wss://tinkernet-rpc.dwellir.com")
+///   .timeout_ms(10000)
+///   .default_mortality(64)
+///   .build();
+/// ```
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct SaturnBuilder {
+    url: Option<String>,
+    label: Option<String>,
+    timeout_ms: Option<u32>,
+    default_mortality: Option<u32>,
+    default_tip: Option<String>,
+}
+
+#[wasm_bindgen]
+impl SaturnBuilder {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> SaturnBuilder {
+        Self::default()
+    }
+
+    /// WebSocket URL of the node to connect to. Required.
+    #[wasm_bindgen]
+    pub fn url(mut self, url: String) -> SaturnBuilder {
+        self.url = Some(url);
+        self
+    }
+
+    /// Prefixes every console log line from the client, e.g. `[tinkernet]`.
+    #[wasm_bindgen]
+    pub fn label(mut self, label: String) -> SaturnBuilder {
+        self.label = Some(label);
+        self
+    }
+
+    /// Bounds connecting, reconnecting and waiting for submitted `Call`s, failing with a
+    /// `Timeout` error instead of leaving the promise pending on an unreachable node.
+    #[wasm_bindgen]
+    pub fn timeout_ms(mut self, timeout_ms: u32) -> SaturnBuilder {
+        self.timeout_ms = Some(timeout_ms);
+        self
+    }
+
+    /// Mortality period used by `Call`s when `sign_and_submit` or `build_payload` aren't given
+    /// one. Without it, transactions default to immortal.
+    #[wasm_bindgen]
+    pub fn default_mortality(mut self, period: u32) -> SaturnBuilder {
+        self.default_mortality = Some(period);
+        self
+    }
+
+    /// Decimal tip used by `Call`s when `sign_and_submit` or `build_payload` aren't given one.
+    #[wasm_bindgen]
+    pub fn default_tip(mut self, tip: String) -> SaturnBuilder {
+        self.default_tip = Some(tip);
+        self
+    }
+
+    /// Connects to the configured URL.
+    #[wasm_bindgen]
+    pub async fn build(self) -> Result<Saturn, SaturnError> {
+        utils::set_panic_hook();
+
+        let url = self.url.ok_or(SaturnError::invalid_input(String::from(
+            "SaturnBuilder needs a url",
+        )))?;
+
+        if let Some(tip) = &self.default_tip {
+            parse_amount(tip)?;
+        }
+
+        let api = timeout::with_timeout(
+            self.timeout_ms,
+            "Connecting",
+            reconnecting_rpc::online_client(&url),
+        )
+        .await??;
+
+        Ok(Saturn {
+            api,
+            url: Some(url),
+            online: true,
+            label: self.label,
+            timeout_ms: self.timeout_ms,
+            default_mortality: self.default_mortality,
+            default_tip: self.default_tip,
+        })
+    }
+}
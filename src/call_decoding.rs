@@ -0,0 +1,108 @@
+use anyhow::anyhow;
+use serde::Serialize;
+use subxt::{
+    ext::scale_value::{self, Composite, Value, ValueDef},
+    Metadata,
+};
+
+/// A runtime call decoded against the metadata, split into its pallet, call and arguments.
+pub struct DecodedCall {
+    pub pallet: String,
+    pub call: String,
+    pub fields: Composite<()>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CallArgument {
+    pub name: Option<String>,
+    pub value: String,
+}
+
+#[derive(Serialize)]
+pub struct CallDescription {
+    pub pallet: String,
+    pub call: String,
+    pub args: Vec<CallArgument>,
+}
+
+impl DecodedCall {
+    /// Human readable form of the call, with argument values rendered by `scale_value`.
+    pub fn describe(&self) -> CallDescription {
+        CallDescription {
+            pallet: self.pallet.clone(),
+            call: self.call.clone(),
+            args: describe_fields(&self.fields),
+        }
+    }
+}
+
+/// Renders each field of a composite, such as call arguments or event fields, by `scale_value`.
+pub fn describe_fields<T>(fields: &Composite<T>) -> Vec<CallArgument> {
+    match fields {
+        Composite::Named(fields) => fields
+            .iter()
+            .map(|(name, value)| CallArgument {
+                name: Some(name.clone()),
+                value: value.to_string(),
+            })
+            .collect(),
+        Composite::Unnamed(fields) => fields
+            .iter()
+            .map(|value| CallArgument {
+                name: None,
+                value: value.to_string(),
+            })
+            .collect(),
+    }
+}
+
+/// Type id of the runtime's outer call enum, taken from the `call` argument of
+/// `INV4.operate_multisig` since v14 metadata doesn't reference it directly.
+pub fn runtime_call_type_id(metadata: &Metadata) -> Option<u32> {
+    metadata
+        .pallet_by_name("INV4")?
+        .call_variant_by_name("operate_multisig")?
+        .fields
+        .iter()
+        .find(|field| field.name.as_deref() == Some("call"))
+        .map(|field| field.ty.id)
+}
+
+/// Decodes SCALE encoded call data into its pallet, call name and argument values.
+pub fn decode_call(metadata: &Metadata, call_data: &[u8]) -> Result<DecodedCall, anyhow::Error> {
+    let type_id =
+        runtime_call_type_id(metadata).ok_or(anyhow!("RuntimeCall type not found in metadata"))?;
+
+    let mut bytes = call_data;
+    let value = scale_value::scale::decode_as_type(&mut bytes, type_id, metadata.types())
+        .map_err(|e| anyhow!("Call data doesn't match the metadata: {:?}", e))?
+        .remove_context();
+
+    if !bytes.is_empty() {
+        return Err(anyhow!(
+            "Call data has {} trailing bytes after the call",
+            bytes.len()
+        ));
+    }
+
+    let ValueDef::Variant(pallet) = value.value else {
+        return Err(anyhow!("Expected the call to decode into a pallet variant"));
+    };
+
+    let Some(Value {
+        value: ValueDef::Variant(call),
+        ..
+    }) = pallet.values.into_values().next()
+    else {
+        return Err(anyhow!(
+            "Expected {} call to decode into a call variant",
+            pallet.name
+        ));
+    };
+
+    Ok(DecodedCall {
+        pallet: pallet.name,
+        call: call.name,
+        fields: call.values,
+    })
+}
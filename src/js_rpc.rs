@@ -0,0 +1,213 @@
+use futures::{
+    channel::mpsc::{self, UnboundedReceiver},
+    Stream,
+};
+use serde_json::value::RawValue;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use subxt::{
+    error::RpcError,
+    rpc::{RpcClientT, RpcFuture, RpcSubscription},
+};
+use wasm_bindgen::{prelude::*, JsCast};
+
+/// `RpcClientT` over a JS provider object, so an app's existing connection can be reused.
+/// The provider has to look like:
+///
+/// ```ts
+/// {
+///   send(method: string, params: unknown[]): Promise<unknown>;
+///   subscribe(
+///     method: string,
+///     params: unknown[],
+///     unsubscribeMethod: string,
+///     onMessage: (result: unknown) => void,
+///   ): Promise<() => void>;
+/// }
+/// ```
+///
+/// `send` matches polkadot-js providers, `subscribe` needs a small adapter around theirs.
+pub struct JsRpcClient {
+    provider: JsValue,
+}
+
+// SAFETY: wasm32 is single threaded, so the provider handle is never sent to or shared with
+// another thread, which is all subxt's `Send + Sync` bound on `RpcClientT` guards against.
+#[cfg(target_arch = "wasm32")]
+unsafe impl Send for JsRpcClient {}
+// SAFETY: as for `Send`, there is no other thread to share the provider handle with.
+#[cfg(target_arch = "wasm32")]
+unsafe impl Sync for JsRpcClient {}
+
+impl JsRpcClient {
+    /// Checks the provider has the `send` and `subscribe` functions the client calls.
+    pub fn new(provider: JsValue) -> Result<JsRpcClient, String> {
+        for name in ["send", "subscribe"] {
+            provider_function(&provider, name)?;
+        }
+
+        Ok(JsRpcClient { provider })
+    }
+
+    async fn request(
+        &self,
+        method: &str,
+        params: Option<Box<RawValue>>,
+    ) -> Result<Box<RawValue>, RpcError> {
+        let send = provider_function(&self.provider, "send").map_err(client_error)?;
+
+        let promise = send
+            .call2(
+                &self.provider,
+                &JsValue::from_str(method),
+                &params_value(params)?,
+            )
+            .map_err(js_error)?;
+
+        let result = wasm_bindgen_futures::JsFuture::from(js_sys::Promise::resolve(&promise))
+            .await
+            .map_err(js_error)?;
+
+        raw_value(&result)
+    }
+
+    async fn subscribe(
+        &self,
+        method: &str,
+        params: Option<Box<RawValue>>,
+        unsubscribe_method: &str,
+    ) -> Result<RpcSubscription, RpcError> {
+        let subscribe = provider_function(&self.provider, "subscribe").map_err(client_error)?;
+
+        let (sender, receiver) = mpsc::unbounded();
+        let on_message = Closure::<dyn FnMut(JsValue)>::new(move |message: JsValue| {
+            let _ = sender.unbounded_send(raw_value(&message));
+        });
+
+        let promise = subscribe
+            .apply(
+                &self.provider,
+                &js_sys::Array::of4(
+                    &JsValue::from_str(method),
+                    &params_value(params)?,
+                    &JsValue::from_str(unsubscribe_method),
+                    on_message.as_ref(),
+                ),
+            )
+            .map_err(js_error)?;
+
+        let unsubscribe = wasm_bindgen_futures::JsFuture::from(js_sys::Promise::resolve(&promise))
+            .await
+            .map_err(js_error)?
+            .dyn_into::<js_sys::Function>()
+            .map_err(|_| {
+                client_error(String::from(
+                    "subscribe has to resolve to an unsubscribe function",
+                ))
+            })?;
+
+        Ok(RpcSubscription {
+            stream: Box::pin(JsSubscription {
+                receiver,
+                unsubscribe,
+                _on_message: on_message,
+            }),
+            id: None,
+        })
+    }
+}
+
+impl RpcClientT for JsRpcClient {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        params: Option<Box<RawValue>>,
+    ) -> RpcFuture<'a, Box<RawValue>> {
+        Box::pin(SendFuture(Box::pin(self.request(method, params))))
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        sub: &'a str,
+        params: Option<Box<RawValue>>,
+        unsub: &'a str,
+    ) -> RpcFuture<'a, RpcSubscription> {
+        Box::pin(SendFuture(Box::pin(self.subscribe(sub, params, unsub))))
+    }
+}
+
+/// Stream of a provider subscription's messages, unsubscribing when dropped.
+struct JsSubscription {
+    receiver: UnboundedReceiver<Result<Box<RawValue>, RpcError>>,
+    unsubscribe: js_sys::Function,
+    _on_message: Closure<dyn FnMut(JsValue)>,
+}
+
+// SAFETY: wasm32 is single threaded, so the stream's JS callbacks are only ever used on the
+// thread that created them.
+#[cfg(target_arch = "wasm32")]
+unsafe impl Send for JsSubscription {}
+
+impl Stream for JsSubscription {
+    type Item = Result<Box<RawValue>, RpcError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl Drop for JsSubscription {
+    fn drop(&mut self) {
+        let _ = self.unsubscribe.call0(&JsValue::NULL);
+    }
+}
+
+/// Future holding JS values, which subxt requires to be `Send`.
+pub struct SendFuture<F>(pub Pin<Box<F>>);
+
+// SAFETY: wasm32 is single threaded, so the future and the JS values it holds are polled and
+// dropped on the thread that created them.
+#[cfg(target_arch = "wasm32")]
+unsafe impl<F> Send for SendFuture<F> {}
+
+impl<F: Future> Future for SendFuture<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+fn provider_function(provider: &JsValue, name: &str) -> Result<js_sys::Function, String> {
+    js_sys::Reflect::get(provider, &JsValue::from_str(name))
+        .ok()
+        .and_then(|function| function.dyn_into::<js_sys::Function>().ok())
+        .ok_or(format!("RPC provider has no {} function", name))
+}
+
+fn params_value(params: Option<Box<RawValue>>) -> Result<JsValue, RpcError> {
+    match params {
+        Some(params) => js_sys::JSON::parse(params.get()).map_err(js_error),
+        None => Ok(js_sys::Array::new().into()),
+    }
+}
+
+fn raw_value(value: &JsValue) -> Result<Box<RawValue>, RpcError> {
+    let json = js_sys::JSON::stringify(value)
+        .ok()
+        .and_then(|json| json.as_string())
+        .unwrap_or_else(|| String::from("null"));
+
+    RawValue::from_string(json).map_err(|e| client_error(e.to_string()))
+}
+
+fn client_error(message: String) -> RpcError {
+    RpcError::ClientError(message.into())
+}
+
+fn js_error(error: JsValue) -> RpcError {
+    client_error(format!("{:?}", error))
+}
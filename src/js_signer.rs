@@ -1,12 +1,18 @@
-use crate::utils::console_log;
+use crate::{
+    call_decoding::{self, CallArgument},
+    utils::console_log,
+    Finality, SaturnError,
+};
 use anyhow::anyhow;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use subxt::{
-    ext::codec::{Compact, Encode},
-    tx::SubmittableExtrinsic,
-    utils::Era,
-    OnlineClient, PolkadotConfig,
+    blocks::ExtrinsicEvents,
+    config::Header,
+    ext::codec::{Compact, Decode, Encode},
+    tx::{SubmittableExtrinsic, TxStatus},
+    utils::{Era, MultiSignature, H256},
+    Metadata, OnlineClient, PolkadotConfig,
 };
 use wasm_bindgen::prelude::*;
 
@@ -16,7 +22,7 @@ extern "C" {
     pub fn json_parse(string: String) -> JsValue;
 }
 
-fn to_hex(bytes: impl AsRef<[u8]>) -> String {
+pub fn to_hex(bytes: impl AsRef<[u8]>) -> String {
     format!("0x{}", hex::encode(bytes.as_ref()))
 }
 
@@ -24,21 +30,66 @@ fn encode_then_hex<E: Encode>(input: &E) -> String {
     format!("0x{}", hex::encode(input.encode()))
 }
 
-pub async fn generate_payload(
+/// Lifetime of a mortal transaction, anchored at the checkpoint block it was built against.
+pub struct Mortality {
+    pub era: Era,
+    pub checkpoint_hash: H256,
+    pub checkpoint_number: u32,
+}
+
+impl Mortality {
+    /// Mortality of a transaction valid for `period` blocks from the current best block.
+    pub async fn from_best_block(
+        api: &OnlineClient<PolkadotConfig>,
+        period: u64,
+    ) -> Result<Mortality, SaturnError> {
+        let header = api
+            .rpc()
+            .header(None)
+            .await?
+            .ok_or(SaturnError::network(String::from(
+                "Best block header not found",
+            )))?;
+
+        Ok(Mortality {
+            era: Era::mortal(period, header.number as u64),
+            checkpoint_hash: header.hash(),
+            checkpoint_number: header.number,
+        })
+    }
+}
+
+/// Extrinsic format version the SDK builds and signs payloads for.
+const SUPPORTED_EXTRINSIC_VERSION: u8 = 4;
+
+/// `mortality` of `None` builds an immortal transaction, checkpointed at the genesis block.
+/// `tip` has to match the tip of the extrinsic params the signature is applied with.
+pub fn generate_payload(
     api: &OnlineClient<PolkadotConfig>,
     account_address: String,
     account_nonce: u64,
     call_data: Vec<u8>,
-) -> String {
+    mortality: Option<&Mortality>,
+    tip: u128,
+) -> Result<String, SaturnError> {
     let genesis_hash = encode_then_hex(&api.genesis_hash());
     // These numbers aren't SCALE encoded; their bytes are just converted to hex:
     let spec_version = to_hex(&api.runtime_version().spec_version.to_be_bytes());
     let transaction_version = to_hex(&api.runtime_version().transaction_version.to_be_bytes());
     let nonce = to_hex(&account_nonce.to_be_bytes());
-    // If you construct a mortal transaction, then this block hash needs to correspond
-    // to the block number passed to `Era::mortal()`.
-    let mortality_checkpoint = encode_then_hex(&api.genesis_hash());
-    let era = encode_then_hex(&Era::Immortal);
+    // For mortal transactions the checkpoint block has to be the one passed to `Era::mortal()`.
+    let (era, mortality_checkpoint, block_number) = match mortality {
+        Some(mortality) => (
+            encode_then_hex(&mortality.era),
+            encode_then_hex(&mortality.checkpoint_hash),
+            to_hex(&mortality.checkpoint_number.to_be_bytes()),
+        ),
+        None => (
+            encode_then_hex(&Era::Immortal),
+            encode_then_hex(&api.genesis_hash()),
+            String::from("0x00000000"),
+        ),
+    };
     let method = to_hex(call_data);
     let signed_extensions: Vec<String> = api
         .metadata()
@@ -47,54 +98,440 @@ pub async fn generate_payload(
         .iter()
         .map(|e| e.identifier().to_string())
         .collect();
-    let tip = encode_then_hex(&Compact(0u128));
+    // The extrinsic params always sign over these, so a runtime without them would reject
+    // the signature.
+    for required in [
+        "CheckSpecVersion",
+        "CheckTxVersion",
+        "CheckGenesis",
+        "CheckMortality",
+        "CheckNonce",
+    ] {
+        if !signed_extensions.iter().any(|e| e == required) {
+            return Err(SaturnError::incompatible_chain(format!(
+                "signed extension {} not found in metadata",
+                required
+            )));
+        }
+    }
+    let tip = encode_then_hex(&Compact(tip));
+    // subxt only builds v4 extrinsics, so a payload for any other format couldn't be applied.
+    let version = api.metadata().extrinsic().version();
+    if version != SUPPORTED_EXTRINSIC_VERSION {
+        return Err(SaturnError::incompatible_chain(format!(
+            "extrinsic version {} is not supported, expected {}",
+            version, SUPPORTED_EXTRINSIC_VERSION
+        )));
+    }
 
     let payload = json!({
         "specVersion": spec_version,
         "transactionVersion": transaction_version,
         "address": account_address,
         "blockHash": mortality_checkpoint,
-        "blockNumber": "0x00000000",
+        "blockNumber": block_number,
         "era": era,
         "genesisHash": genesis_hash,
         "method": method,
         "nonce": nonce,
         "signedExtensions": signed_extensions,
         "tip": tip,
-        "version": 4,
+        "version": version,
     });
 
-    payload.to_string()
+    Ok(payload.to_string())
+}
+
+/// What a payload from `generate_payload` committed the signer to, which the extrinsic the
+/// signature is applied to has to match.
+pub struct SignedPayload {
+    pub address: String,
+    pub nonce: u64,
+    pub era: Era,
+    pub checkpoint_hash: H256,
+    pub tip: u128,
+}
+
+/// Reads the signed fields back out of a `generate_payload` JSON payload.
+pub fn parse_payload(payload: &str) -> Result<SignedPayload, SaturnError> {
+    let payload: serde_json::Value = serde_json::from_str(payload)
+        .map_err(|e| SaturnError::invalid_input(format!("Invalid payload: {}", e)))?;
+
+    let field = |name: &str| {
+        let value = payload.get(name).and_then(|value| value.as_str()).ok_or(
+            SaturnError::invalid_input(format!("Payload is missing {}", name)),
+        )?;
+
+        hex::decode(value.trim_start_matches("0x"))
+            .map_err(|e| SaturnError::invalid_input(format!("Invalid payload {}: {}", name, e)))
+    };
+
+    let decode_error = |name: &str| SaturnError::invalid_input(format!("Invalid payload {}", name));
+
+    let address = payload
+        .get("address")
+        .and_then(|address| address.as_str())
+        .ok_or(SaturnError::invalid_input(String::from(
+            "Payload is missing address",
+        )))?
+        .to_string();
+
+    // The nonce's bytes are converted to hex as is, see `generate_payload`.
+    let nonce: [u8; 8] = field("nonce")?
+        .try_into()
+        .map_err(|_| decode_error("nonce"))?;
+
+    Ok(SignedPayload {
+        address,
+        nonce: u64::from_be_bytes(nonce),
+        era: Era::decode(&mut &field("era")?[..]).map_err(|_| decode_error("era"))?,
+        checkpoint_hash: H256::decode(&mut &field("blockHash")?[..])
+            .map_err(|_| decode_error("blockHash"))?,
+        tip: Compact::<u128>::decode(&mut &field("tip")?[..])
+            .map_err(|_| decode_error("tip"))?
+            .0,
+    })
+}
+
+/// Index and decoded error name of the item that broke a `Utility` batch.
+#[derive(Clone, Debug, Serialize)]
+pub struct BatchFailure {
+    pub index: u32,
+    pub error_name: String,
+}
+
+/// Renders a `DispatchError` as `Pallet.Error` for module errors, using the metadata
+/// to resolve the pallet and error indices.
+pub fn dispatch_error_name(
+    metadata: &Metadata,
+    error: &crate::tinkernet::runtime_types::sp_runtime::DispatchError,
+) -> String {
+    use crate::tinkernet::runtime_types::sp_runtime::DispatchError;
+
+    match error {
+        DispatchError::Module(module_error) => metadata
+            .pallet_by_index(module_error.index)
+            .and_then(|pallet| {
+                pallet
+                    .error_variant_by_index(module_error.error[0])
+                    .map(|variant| format!("{}.{}", pallet.name(), variant.name))
+            })
+            .unwrap_or_else(|| format!("{:?}", module_error)),
+        other => format!("{:?}", other),
+    }
+}
+
+/// The dispatch error of an extrinsic that was included but failed, from its
+/// `System.ExtrinsicFailed` event, named as by `dispatch_error_name`.
+pub fn extrinsic_failure(
+    metadata: &Metadata,
+    events: &ExtrinsicEvents<PolkadotConfig>,
+) -> Result<Option<String>, anyhow::Error> {
+    Ok(events
+        .find_first::<crate::tinkernet::system::events::ExtrinsicFailed>()?
+        .map(|failed| dispatch_error_name(metadata, &failed.dispatch_error)))
+}
+
+/// Looks for `Utility.BatchInterrupted` (batch) or the first `Utility.ItemFailed` (force_batch)
+/// among the extrinsic's events and reports which batched item failed.
+pub fn find_batch_failure(
+    metadata: &Metadata,
+    events: &ExtrinsicEvents<PolkadotConfig>,
+) -> Result<Option<BatchFailure>, anyhow::Error> {
+    if let Some(interrupted) =
+        events.find_first::<crate::tinkernet::utility::events::BatchInterrupted>()?
+    {
+        return Ok(Some(BatchFailure {
+            index: interrupted.index,
+            error_name: dispatch_error_name(metadata, &interrupted.error),
+        }));
+    }
+
+    // force_batch doesn't report indices, so count the per-item events in order instead.
+    let mut index = 0;
+    for event in events.iter() {
+        let event = event?;
+        if event.pallet_name() != "Utility" {
+            continue;
+        }
+
+        match event.variant_name() {
+            "ItemCompleted" => index += 1,
+            "ItemFailed" => {
+                let failed = event
+                    .as_event::<crate::tinkernet::utility::events::ItemFailed>()?
+                    .ok_or(anyhow!("ItemFailed could not be decoded"))?;
+
+                return Ok(Some(BatchFailure {
+                    index,
+                    error_name: dispatch_error_name(metadata, &failed.error),
+                }));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(None)
 }
 
-pub async fn submit_wait_inblock_and_get_event(
+/// Submits the extrinsic, waits for it to reach `finality` and collects its events.
+///
+/// With `event_pallets` only the events it names, as whole pallets like `INV4` or single events
+/// like `INV4.CoreCreated`, are decoded and collected, which is much cheaper on busy blocks.
+/// Otherwise every event of the extrinsic is.
+///
+/// RPC errors keep subxt's details, so pool rejections like `ErrorKind::StaleNonce` can be
+/// told apart from network failures.
+pub async fn submit_and_collect_events(
+    metadata: &Metadata,
+    label: Option<&str>,
+    event_pallets: Option<&[String]>,
+    finality: Finality,
+    on_progress: Option<&js_sys::Function>,
     extrinsic: SubmittableExtrinsic<PolkadotConfig, OnlineClient<PolkadotConfig>>,
-) -> Result<crate::tinkernet::system::events::ExtrinsicSuccess, anyhow::Error> {
-    let events = extrinsic
+) -> Result<Submission, SaturnError> {
+    let mut progress = extrinsic
         .submit_and_watch()
-        .await?
-        .wait_for_in_block()
-        .await?
-        .fetch_events()
-        .await?;
-
-    let events_str = format!("{:?}", &events);
-    console_log!("{}", events_str);
-    for event in events.find::<crate::tinkernet::system::events::ExtrinsicSuccess>() {
-        console_log!("{:?}", event);
+        .await
+        .map_err(SaturnError::from)?;
+    let in_block = loop {
+        let status = progress
+            .next_item()
+            .await
+            .ok_or(SaturnError::network(String::from(
+                "Transaction status subscription ended early",
+            )))?
+            .map_err(SaturnError::from)?;
+
+        if let Some(on_progress) = on_progress {
+            report_progress(label, on_progress, &status);
+        }
+
+        match status {
+            TxStatus::InBlock(in_block) if matches!(finality, Finality::InBlock) => break in_block,
+            TxStatus::Finalized(in_block) => break in_block,
+            TxStatus::FinalityTimeout(_) => {
+                return Err(SaturnError::timeout(String::from(
+                    "Transaction's block wasn't finalized in time",
+                )))
+            }
+            TxStatus::Usurped(_) => {
+                return Err(SaturnError::dropped(String::from(
+                    "Transaction was replaced by one with the same nonce",
+                )))
+            }
+            TxStatus::Dropped => {
+                return Err(SaturnError::dropped(String::from(
+                    "Transaction was dropped from the pool",
+                )))
+            }
+            TxStatus::Invalid => {
+                return Err(SaturnError::invalid_transaction(String::from(
+                    "Transaction is invalid",
+                )))
+            }
+            _ => continue,
+        }
+    };
+    let events = in_block.fetch_events().await.map_err(SaturnError::from)?;
+
+    let mut described_events = Vec::new();
+    for event in events.iter() {
+        let event = event.map_err(SaturnError::from)?;
+        if event_pallets.map_or(false, |names| {
+            !names
+                .iter()
+                .any(|name| event_matches(name, event.pallet_name(), event.variant_name()))
+        }) {
+            continue;
+        }
+
+        let fields = event.field_values().map_err(SaturnError::from)?;
+        console_log!(
+            label = label;
+            "{}.{}: {:?}",
+            event.pallet_name(),
+            event.variant_name(),
+            fields
+        );
+
+        described_events.push(SubmittedEvent {
+            pallet: event.pallet_name().to_string(),
+            event: event.variant_name().to_string(),
+            fields: call_decoding::describe_fields(&fields),
+        });
+    }
+
+    let decode_error = |e: anyhow::Error| SaturnError::encoding(e.to_string());
+
+    let batch_failure = find_batch_failure(metadata, &events).map_err(decode_error)?;
+
+    // Failed dispatches are still included, so they're reported in the result rather than as
+    // an error.
+    let error =
+        if let Some(error_name) = extrinsic_failure(metadata, &events).map_err(decode_error)? {
+            Some(format!("Extrinsic failed: {}", error_name))
+        } else {
+            batch_failure.as_ref().map(|failure| {
+                format!(
+                    "Batch item {} failed: {}",
+                    failure.index, failure.error_name
+                )
+            })
+        };
+
+    Ok(Submission {
+        tx_hash: in_block.extrinsic_hash(),
+        block_hash: in_block.block_hash(),
+        error,
+        batch_failure,
+        described_events,
+        events,
+    })
+}
+
+/// Whether `name`, either `Pallet` or `Pallet.Event`, names the event.
+fn event_matches(name: &str, pallet: &str, event: &str) -> bool {
+    match name.split_once('.') {
+        Some((name_pallet, name_event)) => name_pallet == pallet && name_event == event,
+        None => name == pallet,
+    }
+}
+
+/// What `report_progress` passes the progress callback.
+#[derive(Serialize)]
+struct ProgressUpdate {
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block_hash: Option<String>,
+}
+
+/// Calls `on_progress` with `{ status, block_hash? }` for a transaction status. Exceptions the
+/// callback throws are logged and otherwise ignored, so they can't abort the submission.
+fn report_progress(
+    label: Option<&str>,
+    on_progress: &js_sys::Function,
+    status: &TxStatus<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+) {
+    let (status, block_hash) = match status {
+        TxStatus::Future => ("future", None),
+        TxStatus::Ready => ("ready", None),
+        TxStatus::Broadcast(_) => ("broadcast", None),
+        TxStatus::InBlock(in_block) => ("in_block", Some(to_hex(in_block.block_hash()))),
+        TxStatus::Retracted(block_hash) => ("retracted", Some(to_hex(block_hash))),
+        TxStatus::FinalityTimeout(block_hash) => ("finality_timeout", Some(to_hex(block_hash))),
+        TxStatus::Finalized(in_block) => ("finalized", Some(to_hex(in_block.block_hash()))),
+        TxStatus::Usurped(_) => ("usurped", None),
+        TxStatus::Dropped => ("dropped", None),
+        TxStatus::Invalid => ("invalid", None),
+    };
+
+    let update = match serde_wasm_bindgen::to_value(&ProgressUpdate { status, block_hash }) {
+        Ok(update) => update,
+        Err(e) => {
+            console_log!(label = label; "rust progress update error: {:?}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = on_progress.call1(&JsValue::null(), &update) {
+        console_log!(label = label; "progress callback threw: {:?}", e);
+    }
+}
+
+/// An event emitted by a submitted extrinsic, with its field values rendered by `scale_value`.
+#[derive(Debug, Serialize)]
+pub struct SubmittedEvent {
+    pub pallet: String,
+    pub event: String,
+    pub fields: Vec<CallArgument>,
+}
+
+/// Where a submitted extrinsic was included, how its dispatch went and what it emitted.
+pub struct Submission {
+    pub tx_hash: H256,
+    pub block_hash: H256,
+    pub error: Option<String>,
+    pub batch_failure: Option<BatchFailure>,
+    pub described_events: Vec<SubmittedEvent>,
+    pub events: ExtrinsicEvents<PolkadotConfig>,
+}
+
+impl Submission {
+    /// The `CoreCreated` event, present when the extrinsic created a core.
+    pub fn core_created(
+        &self,
+    ) -> Result<Option<crate::tinkernet::inv4::events::CoreCreated>, anyhow::Error> {
+        Ok(self
+            .events
+            .find_first::<crate::tinkernet::inv4::events::CoreCreated>()?)
     }
+}
+
+/// Messages extensions reject a signing request with when the user dismisses it.
+const USER_REJECTION_MESSAGES: [&str; 2] = [
+    // polkadot-js and Talisman.
+    "Cancelled",
+    // SubWallet.
+    "Rejected by user",
+];
 
-    let core_created_event = events
-        .find_first::<crate::tinkernet::inv4::events::CoreCreated>()?
-        .unwrap();
+/// EIP-1193's `userRejectedRequest` code, which Talisman and SubWallet also set.
+const USER_REJECTION_CODE: f64 = 4001.0;
 
-    console_log!("core_created_event: {:#?}", core_created_event);
+/// Whether a signer's rejection is the user cancelling, going by the exact errors wallets
+/// reject with, see `USER_REJECTION_MESSAGES` and `USER_REJECTION_CODE`.
+pub fn is_user_rejection(error: &JsValue) -> bool {
+    let field = |name: &str| js_sys::Reflect::get(error, &JsValue::from_str(name)).ok();
+
+    if field("code").and_then(|code| code.as_f64()) == Some(USER_REJECTION_CODE) {
+        return true;
+    }
 
-    let success = events.find_first::<crate::tinkernet::system::events::ExtrinsicSuccess>()?;
-    success.ok_or(anyhow!("ExtrinsicSuccess not found in events"))
+    error
+        .as_string()
+        .or_else(|| field("message").and_then(|message| message.as_string()))
+        .is_some_and(|message| USER_REJECTION_MESSAGES.contains(&message.as_str()))
 }
 
 #[derive(Deserialize)]
 pub struct SignatureResponse {
     pub signature: String,
+    /// `sr25519`, `ed25519` or `ecdsa` when `signature` is the raw signature rather than
+    /// an encoded `MultiSignature`.
+    pub scheme: Option<String>,
+}
+
+/// Wraps `signature` into a `MultiSignature`. Without a `scheme` it has to already be an
+/// encoded `MultiSignature`, otherwise it's taken as the raw signature of that scheme.
+pub fn multi_signature(
+    signature: &[u8],
+    scheme: Option<&str>,
+) -> Result<MultiSignature, SaturnError> {
+    let invalid_length = |expected: usize| {
+        SaturnError::signature(format!(
+            "Expected a {} byte {} signature, got {} bytes",
+            expected,
+            scheme.unwrap_or_default(),
+            signature.len()
+        ))
+    };
+
+    match scheme {
+        None => MultiSignature::decode(&mut &signature[..])
+            .map_err(|_| SaturnError::signature(String::from("MultiSignature Decoding"))),
+        Some("sr25519") => Ok(MultiSignature::Sr25519(
+            signature.try_into().map_err(|_| invalid_length(64))?,
+        )),
+        Some("ed25519") => Ok(MultiSignature::Ed25519(
+            signature.try_into().map_err(|_| invalid_length(64))?,
+        )),
+        Some("ecdsa") => Ok(MultiSignature::Ecdsa(
+            signature.try_into().map_err(|_| invalid_length(65))?,
+        )),
+        Some(scheme) => Err(SaturnError::signature(format!(
+            "Unknown signature scheme {}, expected sr25519, ed25519 or ecdsa",
+            scheme
+        ))),
+    }
 }
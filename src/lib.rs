@@ -1,18 +1,41 @@
+mod builder;
+mod call_decoding;
+#[cfg(target_arch = "wasm32")]
+mod js_rpc;
 mod js_signer;
+mod offline_rpc;
+mod reconnecting_rpc;
+mod ss58;
+mod submit_options;
+mod subscription;
+mod timeout;
 mod utils;
 
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use builder::SaturnBuilder;
+use futures::StreamExt;
 use js_signer::SignatureResponse;
-use sp_arithmetic::per_things::Perbill;
+use serde::Serialize;
+use sp_arithmetic::per_things::{PerThing, Perbill};
+use submit_options::SubmitOptions;
+use subscription::Subscription;
 use subxt::{
+    blocks::ExtrinsicEvents,
+    config::polkadot::{PlainTip, PolkadotExtrinsicParamsBuilder},
     ext::{
-        codec::{Decode, Encode},
+        codec::{Compact, Decode, Encode},
         scale_value::{Composite, Value},
     },
-    tx::Payload,
-    utils::{AccountId32, MultiSignature},
-    Error as SubxtError, OnlineClient, PolkadotConfig,
+    rpc::types::{DryRunResult, RuntimeVersion, StorageKey},
+    storage::{
+        address::{StorageAddress, Yes},
+        Storage,
+    },
+    tx::{PartialExtrinsic, Payload, SubmittableExtrinsic},
+    utils::{AccountId32, MultiSignature, H256},
+    Error as SubxtError, Metadata, OnlineClient, PolkadotConfig,
 };
 use wasm_bindgen::prelude::*;
 
@@ -26,229 +49,4623 @@ use wasm_bindgen::prelude::*;
 )]
 pub mod tinkernet {}
 
-use tinkernet::runtime_types::pallet_inv4::fee_handling::FeeAsset;
+use tinkernet::runtime_types::{
+    bounded_collections::bounded_vec::BoundedVec,
+    pallet_inv4::{fee_handling::FeeAsset, multisig::MultisigOperation, voting::Tally},
+};
+
+type MultisigProposal = MultisigOperation<AccountId32, Tally, BoundedVec<u8>, BoundedVec<u8>>;
+
+type SignedExtrinsic = SubmittableExtrinsic<PolkadotConfig, OnlineClient<PolkadotConfig>>;
+
+/// What went wrong, so callers can e.g. retry network failures but not rejected signatures.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    Network,
+    Encoding,
+    Signature,
+    Dispatch,
+    InvalidInput,
+    IncompatibleChain,
+    Timeout,
+    /// The user cancelled signing in their wallet.
+    UserRejected,
+    /// The caller stopped waiting through an `AbortSignal`.
+    Aborted,
+    /// The transaction pool rejected the transaction for its nonce, see
+    /// `SubmitOptions.retry_on_stale_nonce`.
+    StaleNonce,
+    /// The transaction pool rejected the transaction for anything but its nonce, e.g. because
+    /// its signer can't pay the fees.
+    InvalidTransaction,
+    /// The transaction left the pool without being included, dropped or replaced by another
+    /// with the same nonce.
+    Dropped,
+}
 
 #[wasm_bindgen]
 #[derive(Debug)]
-pub struct SaturnError(String);
+pub struct SaturnError {
+    kind: ErrorKind,
+    message: String,
+    details: Option<String>,
+}
 
 impl SaturnError {
+    /// The error message, without its kind or details.
     pub fn inner(self) -> String {
-        self.0
+        self.message
+    }
+
+    fn new(kind: ErrorKind, message: String) -> Self {
+        SaturnError {
+            kind,
+            message,
+            details: None,
+        }
+    }
+
+    fn network(message: String) -> Self {
+        Self::new(ErrorKind::Network, message)
+    }
+
+    fn encoding(message: String) -> Self {
+        Self::new(ErrorKind::Encoding, message)
+    }
+
+    fn signature(message: String) -> Self {
+        Self::new(ErrorKind::Signature, message)
+    }
+
+    fn dispatch(message: String) -> Self {
+        Self::new(ErrorKind::Dispatch, message)
+    }
+
+    fn invalid_input(message: String) -> Self {
+        Self::new(ErrorKind::InvalidInput, message)
+    }
+
+    fn user_rejected(message: String) -> Self {
+        Self::new(ErrorKind::UserRejected, message)
+    }
+
+    fn aborted(message: String) -> Self {
+        Self::new(ErrorKind::Aborted, message)
+    }
+
+    fn invalid_transaction(message: String) -> Self {
+        Self::new(ErrorKind::InvalidTransaction, message)
+    }
+
+    fn dropped(message: String) -> Self {
+        Self::new(ErrorKind::Dropped, message)
+    }
+
+    fn timeout(message: String) -> Self {
+        Self::new(ErrorKind::Timeout, message)
+    }
+
+    fn incompatible_chain(reason: String) -> Self {
+        Self::new(ErrorKind::IncompatibleChain, reason)
+    }
+}
+
+#[wasm_bindgen]
+impl SaturnError {
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        self.message.clone()
+    }
+
+    /// Debug output of the underlying error, when there is one.
+    #[wasm_bindgen(getter)]
+    pub fn details(&self) -> Option<String> {
+        self.details.clone()
     }
 }
 
 impl From<SubxtError> for SaturnError {
     fn from(value: SubxtError) -> Self {
-        SaturnError(format!("{:?}", value))
+        let details = format!("{:?}", value);
+
+        let kind = match &value {
+            SubxtError::Runtime(_) | SubxtError::Transaction(_) => ErrorKind::Dispatch,
+            SubxtError::Codec(_)
+            | SubxtError::Serialization(_)
+            | SubxtError::Metadata(_)
+            | SubxtError::Decode(_)
+            | SubxtError::Encode(_) => ErrorKind::Encoding,
+            SubxtError::Rpc(_) => pool_error_kind(&details).unwrap_or(ErrorKind::Network),
+            _ => ErrorKind::Network,
+        };
+
+        SaturnError {
+            kind,
+            message: value.to_string(),
+            details: Some(details),
+        }
+    }
+}
+
+/// Converts a fraction between 0 and 1 into the Perbill parts `create_core` and
+/// `set_parameters` take, e.g. 0.5 into 500_000_000.
+#[wasm_bindgen]
+pub fn perbill_from_fraction(fraction: f64) -> Result<u32, SaturnError> {
+    if !(0.0..=1.0).contains(&fraction) {
+        return Err(SaturnError::invalid_input(format!(
+            "{} is not a fraction between 0 and 1",
+            fraction
+        )));
+    }
+
+    Ok((fraction * Perbill::ACCURACY as f64).round() as u32)
+}
+
+/// Converts a percentage between 0 and 100 into Perbill parts, e.g. 50 into 500_000_000.
+#[wasm_bindgen]
+pub fn perbill_from_percent(percent: f64) -> Result<u32, SaturnError> {
+    if !(0.0..=100.0).contains(&percent) {
+        return Err(SaturnError::invalid_input(format!(
+            "{} is not a percentage between 0 and 100",
+            percent
+        )));
+    }
+
+    perbill_from_fraction(percent / 100.0)
+}
+
+/// Converts Perbill parts back into a percentage, e.g. 500_000_000 into 50.
+#[wasm_bindgen]
+pub fn perbill_to_percent(parts: u32) -> f64 {
+    parts as f64 / Perbill::ACCURACY as f64 * 100.0
+}
+
+/// Weight bought on the destination chain for a remote call, see `Saturn.send_xcm_call`.
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct XcmWeight {
+    ref_time: u64,
+    proof_size: u64,
+}
+
+#[wasm_bindgen]
+impl XcmWeight {
+    #[wasm_bindgen(constructor)]
+    pub fn new(ref_time: u64, proof_size: u64) -> XcmWeight {
+        XcmWeight {
+            ref_time,
+            proof_size,
+        }
+    }
+}
+
+/// How far a submission has to get before it resolves.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Finality {
+    InBlock,
+    Finalized,
+}
+
+/// A fee asset, by its runtime `FeeAsset` variant name. `TNKR` and `KSM` are built in, and
+/// `Saturn.fee_asset` looks up others in the connected runtime's metadata, so assets added by
+/// a runtime upgrade can be used without an SDK release.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsFeeAsset {
+    name: String,
+}
+
+#[wasm_bindgen]
+impl JsFeeAsset {
+    #[wasm_bindgen(js_name = TNKR)]
+    pub fn tnkr() -> JsFeeAsset {
+        JsFeeAsset {
+            name: String::from("TNKR"),
+        }
+    }
+
+    #[wasm_bindgen(js_name = KSM)]
+    pub fn ksm() -> JsFeeAsset {
+        JsFeeAsset {
+            name: String::from("KSM"),
+        }
+    }
+
+    /// The runtime `FeeAsset` variant name.
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl JsFeeAsset {
+    fn is_tnkr(&self) -> bool {
+        self.name == "TNKR"
+    }
+
+    fn is_ksm(&self) -> bool {
+        self.name == "KSM"
+    }
+
+    /// The `FeeAsset` variant, encoded by name so variants unknown to the bundled metadata work.
+    fn to_value(&self) -> Value {
+        Value::unnamed_variant(self.name.clone(), [])
+    }
+}
+
+impl TryFrom<JsFeeAsset> for FeeAsset {
+    type Error = SaturnError;
+
+    /// The bundled metadata's `FeeAsset`, which only has the built in variants.
+    fn try_from(value: JsFeeAsset) -> Result<Self, Self::Error> {
+        match value.name.as_str() {
+            "TNKR" => Ok(FeeAsset::TNKR),
+            "KSM" => Ok(FeeAsset::KSM),
+            name => Err(SaturnError::invalid_input(format!(
+                "Fee asset {} is not in the bundled metadata",
+                name
+            ))),
+        }
     }
 }
 
+/// The `CoreCreated` event of a submission that created a core.
 #[wasm_bindgen]
 #[derive(Clone)]
-pub enum JsFeeAsset {
-    TNKR,
-    KSM,
+pub struct CoreCreationResult {
+    core_id: u32,
+    core_account: String,
+    metadata: String,
+    minimum_support: f64,
+    required_approval: f64,
+}
+
+#[wasm_bindgen]
+impl CoreCreationResult {
+    #[wasm_bindgen(getter)]
+    pub fn core_id(&self) -> u32 {
+        self.core_id
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn core_account(&self) -> String {
+        self.core_account.clone()
+    }
+
+    /// The metadata as text when it's valid UTF-8, and as hex otherwise.
+    #[wasm_bindgen(getter)]
+    pub fn metadata(&self) -> String {
+        self.metadata.clone()
+    }
+
+    /// As a fraction between 0 and 1.
+    #[wasm_bindgen(getter)]
+    pub fn minimum_support(&self) -> f64 {
+        self.minimum_support
+    }
+
+    /// As a fraction between 0 and 1.
+    #[wasm_bindgen(getter)]
+    pub fn required_approval(&self) -> f64 {
+        self.required_approval
+    }
+}
+
+/// Outcome of `Call.sign_and_submit`. An extrinsic whose dispatch failed is still included, so
+/// it comes back with `success` false and the dispatch error in `error`.
+#[wasm_bindgen]
+pub struct SubmissionResult {
+    tx_hash: String,
+    block_hash: String,
+    error: Option<String>,
+    batch_failure: Option<js_signer::BatchFailure>,
+    events: Vec<js_signer::SubmittedEvent>,
+    core_created: Option<CoreCreationResult>,
+    predicted_state: Vec<PredictedChange>,
+}
+
+#[wasm_bindgen]
+impl SubmissionResult {
+    #[wasm_bindgen(getter)]
+    pub fn tx_hash(&self) -> String {
+        self.tx_hash.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn block_hash(&self) -> String {
+        self.block_hash.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    /// `error` as a `SaturnError` of kind `Dispatch`, naming the failing pallet and error such
+    /// as `INV4.NotEnoughVotesToExecute`, for callers that want to throw it.
+    #[wasm_bindgen(getter)]
+    pub fn dispatch_error(&self) -> Option<SaturnError> {
+        self.error.clone().map(|error| SaturnError {
+            details: Some(format!(
+                "extrinsic {} in block {}",
+                self.tx_hash, self.block_hash
+            )),
+            ..SaturnError::dispatch(error)
+        })
+    }
+
+    /// `{ index, error_name }` of the item that failed when the call was a `Utility.batch` that
+    /// was interrupted or a `Utility.force_batch` that had items fail, with `error_name` like
+    /// `INV4.NoPermission`, and undefined otherwise. `error` describes the same failure as text.
+    ///
+    /// Batches built by `Saturn.batch` and `operate_multisig_batch` are `Utility.batch_all`,
+    /// which reverts the whole extrinsic instead and emits neither event. For those this is
+    /// always undefined, and `error` carries the failed item's error from `ExtrinsicFailed`,
+    /// without its index.
+    #[wasm_bindgen(getter)]
+    pub fn batch_failure(&self) -> Result<JsValue, SaturnError> {
+        serde_wasm_bindgen::to_value(&self.batch_failure)
+            .map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// The extrinsic's events as `[{ pallet, event, fields }]`, limited to the pallets and
+    /// events given to `Call.with_event_pallets` if any.
+    #[wasm_bindgen(getter)]
+    pub fn events(&self) -> Result<JsValue, SaturnError> {
+        serde_wasm_bindgen::to_value(&self.events).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// Set when the call created a core.
+    #[wasm_bindgen(getter)]
+    pub fn core_created(&self) -> Option<CoreCreationResult> {
+        self.core_created.clone()
+    }
+
+    /// State changes the extrinsic's INV4 events imply, so UIs can update without refetching,
+    /// as `[{ kind, core_id, ... }]` in event order. `kind` is one of:
+    ///
+    /// - `minted` / `burned`: `account`'s balance of the core's token changed by `amount`.
+    /// - `proposal_opened` / `vote_added`: `account` voted on `call_hash`, which now has
+    ///   `ayes` and `nays`.
+    /// - `vote_withdrawn`: `account` withdrew its vote on `call_hash`.
+    /// - `proposal_executed` / `proposal_canceled`: `call_hash` is no longer pending.
+    /// - `parameters_set`: the core's `metadata`, `minimum_support`, `required_approval` or
+    ///   `frozen_tokens` changed to the values given, the others are unchanged.
+    ///
+    /// This is best effort: it only reflects this extrinsic, so concurrent changes by others
+    /// still need a refetch to show up.
+    #[wasm_bindgen(getter)]
+    pub fn predicted_state(&self) -> Result<JsValue, SaturnError> {
+        serde_wasm_bindgen::to_value(&self.predicted_state)
+            .map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+}
+
+/// Upper bound on how many blocks a single event scan may cover.
+const MAX_EVENT_SCAN_BLOCKS: u32 = 1_000;
+
+/// Page size used when walking storage maps key by key.
+const STORAGE_PAGE_SIZE: u32 = 100;
+
+/// Most votes `proposal_votes` returns per page.
+const MAX_VOTES_PAGE: u32 = 500;
+
+/// Kusama parachains `Rings.send_call` can reach, by para id and `Chains` variant.
+const RINGS_DESTINATIONS: [(u32, &str); 2] = [(2090, "Basilisk"), (2087, "Picasso")];
+
+/// Pallets the SDK builds its calls and queries on.
+const REQUIRED_PALLETS: [&str; 3] = ["INV4", "CoreAssets", "Utility"];
+
+/// Calls of `REQUIRED_PALLETS` the SDK builds, by pallet and call name.
+const REQUIRED_CALLS: [(&str, &str); 10] = [
+    ("INV4", "create_core"),
+    ("INV4", "operate_multisig"),
+    ("INV4", "vote_multisig"),
+    ("INV4", "withdraw_vote_multisig"),
+    ("INV4", "cancel_multisig_proposal"),
+    ("INV4", "set_parameters"),
+    ("INV4", "token_mint"),
+    ("INV4", "token_burn"),
+    ("CoreAssets", "transfer"),
+    ("Utility", "batch_all"),
+];
+
+#[derive(Serialize)]
+pub struct VoteOutlook {
+    ayes: String,
+    nays: String,
+    undecided: String,
+    passes_now: bool,
+    can_pass: bool,
+    guaranteed_to_pass: bool,
+    decisive_members: Vec<String>,
+}
+
+/// A state change a submission's events imply, see `SubmissionResult.predicted_state`.
+#[derive(Serialize)]
+pub struct PredictedChange {
+    kind: &'static str,
+    core_id: u32,
+    account: Option<String>,
+    amount: Option<String>,
+    call_hash: Option<String>,
+    ayes: Option<String>,
+    nays: Option<String>,
+    metadata: Option<String>,
+    minimum_support: Option<f64>,
+    required_approval: Option<f64>,
+    frozen_tokens: Option<bool>,
+}
+
+impl PredictedChange {
+    fn new(kind: &'static str, core_id: u32) -> Self {
+        PredictedChange {
+            kind,
+            core_id,
+            account: None,
+            amount: None,
+            call_hash: None,
+            ayes: None,
+            nays: None,
+            metadata: None,
+            minimum_support: None,
+            required_approval: None,
+            frozen_tokens: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct GovernanceActivity {
+    block_number: u32,
+    kind: &'static str,
+    core_id: u32,
+    call_hash: Option<String>,
+    aye: Option<bool>,
+    amount: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ExecutionWeight {
+    ref_time: u64,
+    proof_size: u64,
+    max_ref_time: u64,
+    max_proof_size: u64,
+    fits_in_block: bool,
+}
+
+#[derive(Serialize)]
+pub struct ProposalOutlook {
+    call_hash: String,
+    can_pass: bool,
+    ayes_needed: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct CoreInfo {
+    account: String,
+    metadata: String,
+    minimum_support: f64,
+    required_approval: f64,
+    frozen_tokens: bool,
+}
+
+#[derive(Serialize)]
+pub struct VotingBalance {
+    free: String,
+    reserved: String,
+    frozen: String,
+}
+
+#[derive(Serialize)]
+pub struct AccountCore {
+    core_id: u32,
+    free: String,
+    reserved: String,
+}
+
+#[derive(Serialize)]
+pub struct DryRunOutcome {
+    success: bool,
+    failure: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct TokenProperties {
+    symbol: String,
+    decimals: u32,
+}
+
+#[derive(Serialize)]
+pub struct ChainProperties {
+    token_symbol: Option<String>,
+    token_decimals: Option<u32>,
+    ss58_prefix: Option<u16>,
+    tokens: Vec<TokenProperties>,
+}
+
+#[derive(Serialize)]
+pub struct PingInfo {
+    chain_name: String,
+    spec_version: u32,
+    genesis_hash: String,
+    connected: bool,
+}
+
+#[derive(Serialize)]
+pub struct BlockInfo {
+    number: u32,
+    hash: String,
+}
+
+#[derive(Serialize)]
+pub struct CoreEvent {
+    block_number: u32,
+    block_hash: String,
+    event: String,
+    fields: Vec<call_decoding::CallArgument>,
+}
+
+#[derive(Serialize)]
+pub struct SubscriptionError {
+    error: String,
+}
+
+#[derive(Serialize)]
+pub struct CoreMember {
+    account: String,
+    weight: String,
+    share: f64,
+}
+
+#[derive(Serialize)]
+pub struct ProposalWithCall {
+    call_hash: String,
+    call: Option<call_decoding::CallDescription>,
+    call_data: String,
+    proposer: String,
+    metadata: Option<String>,
+    ayes: String,
+    nays: String,
+    voter_count: u32,
+}
+
+#[derive(Serialize, Default)]
+pub struct ProposalChanges {
+    added: Vec<ProposalWithCall>,
+    removed: Vec<String>,
+    updated: Vec<ProposalWithCall>,
+}
+
+impl ProposalChanges {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+}
+
+#[derive(Serialize)]
+pub struct CoreAsset {
+    asset: String,
+    free: String,
+    reserved: String,
+}
+
+#[derive(Serialize)]
+pub struct CoreSummary {
+    core_id: u32,
+    metadata: String,
+}
+
+#[derive(Serialize)]
+pub struct CoresPage {
+    cores: Vec<CoreSummary>,
+    next_key: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ProposalsPage {
+    proposals: Vec<ProposalWithCall>,
+    next_key: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct AccountVote {
+    voted: bool,
+    aye: Option<bool>,
+    weight: String,
+}
+
+#[derive(Serialize)]
+pub struct ProposalVote {
+    account: String,
+    aye: bool,
+    weight: String,
+}
+
+#[derive(Serialize)]
+pub struct ProposalVotes {
+    votes: Vec<ProposalVote>,
+    ayes: String,
+    nays: String,
+    voter_count: u32,
+}
+
+#[derive(Serialize)]
+pub struct CoreHealth {
+    member_count: u32,
+    total_issuance: String,
+    can_pass_votes: bool,
+    warnings: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct FeeAssetInfo {
+    index: u8,
+    symbol: String,
+}
+
+#[derive(Serialize)]
+pub struct ChainState {
+    genesis_hash: String,
+    finalized_number: u32,
+    finalized_hash: String,
+    spec_version: u32,
+    chain_name: String,
+}
+
+#[derive(Serialize)]
+pub struct EraReward {
+    era: u32,
+    amount: String,
+}
+
+#[derive(Serialize)]
+pub struct CoreRewards {
+    total: String,
+    eras: Vec<EraReward>,
+}
+
+#[derive(Serialize)]
+pub struct CoreAccount {
+    core_id: u32,
+    address: String,
+}
+
+#[derive(Serialize)]
+pub struct NamedReserve {
+    id: String,
+    amount: String,
+}
+
+#[derive(Serialize)]
+pub struct CoreReserve {
+    core_id: u32,
+    amount: String,
+}
+
+#[derive(Serialize)]
+pub struct ReservedBalances {
+    total_reserved: String,
+    named_reserves: Vec<NamedReserve>,
+    by_core: Vec<CoreReserve>,
+    note: Option<String>,
+}
+
+#[wasm_bindgen]
+pub struct Saturn {
+    api: OnlineClient<PolkadotConfig>,
+    /// `None` when built from an app's RPC provider, which it then has to reconnect itself.
+    url: Option<String>,
+    /// `false` for clients built with `new_offline`, whose `Call`s can't be submitted.
+    online: bool,
+    label: Option<String>,
+    timeout_ms: Option<u32>,
+    default_mortality: Option<u32>,
+    default_tip: Option<String>,
 }
 
-impl From<JsFeeAsset> for FeeAsset {
-    fn from(value: JsFeeAsset) -> Self {
-        match value {
-            JsFeeAsset::TNKR => FeeAsset::TNKR,
-            JsFeeAsset::KSM => FeeAsset::KSM,
+#[wasm_bindgen]
+impl Saturn {
+    /// `label` optionally prefixes every console log line from this instance, e.g. `[tinkernet]`.
+    ///
+    /// `timeout_ms` bounds connecting, reconnecting and waiting for submitted `Call`s, failing
+    /// with a `Timeout` error instead of leaving the promise pending on an unreachable node.
+    ///
+    /// Further options are only available through `SaturnBuilder`.
+    #[wasm_bindgen(constructor)]
+    pub async fn new(
+        url: String,
+        label: Option<String>,
+        timeout_ms: Option<u32>,
+    ) -> Result<Saturn, SaturnError> {
+        let mut builder = SaturnBuilder::new().url(url);
+        if let Some(label) = label {
+            builder = builder.label(label);
+        }
+        if let Some(timeout_ms) = timeout_ms {
+            builder = builder.timeout_ms(timeout_ms);
+        }
+
+        builder.build().await
+    }
+
+    /// Whether the node still answers, e.g. to detect a dropped WebSocket connection.
+    #[wasm_bindgen]
+    pub async fn is_connected(&self) -> bool {
+        self.api.rpc().system_health().await.is_ok()
+    }
+
+    /// Builds the client on an RPC provider the app already has, instead of opening another
+    /// WebSocket. See `js_rpc::JsRpcClient` for the shape `provider` needs; objects without
+    /// its `send` and `subscribe` functions are rejected with an `InvalidInput` error.
+    ///
+    /// Reconnecting is left to the app's provider, so `reconnect` isn't available.
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen]
+    pub async fn from_rpc_client(
+        provider: JsValue,
+        label: Option<String>,
+    ) -> Result<Saturn, SaturnError> {
+        utils::set_panic_hook();
+
+        let rpc_client = js_rpc::JsRpcClient::new(provider).map_err(SaturnError::invalid_input)?;
+
+        let api = OnlineClient::<PolkadotConfig>::from_rpc_client(std::sync::Arc::new(rpc_client))
+            .await?;
+
+        Ok(Self {
+            api,
+            url: None,
+            online: true,
+            label,
+            timeout_ms: None,
+            default_mortality: None,
+            default_tip: None,
+        })
+    }
+
+    /// Builds a client without connecting, from metadata saved with `export_metadata`, the
+    /// chain's hex encoded genesis hash and the spec and transaction version of the runtime
+    /// the metadata belongs to.
+    ///
+    /// Offline clients build calls and their signer payloads, as long as `build_payload` is
+    /// given a `nonce` and no `mortality_period`, which both need the chain. Anything reading the chain fails with a
+    /// `Network` error, and submitting their `Call`s with an `InvalidInput` error. To submit
+    /// later, rebuild the call on a connected client with `call_from_bytes` and pass it the
+    /// signed payload through `submit_with_signature`.
+    #[wasm_bindgen]
+    pub fn new_offline(
+        metadata_bytes: Vec<u8>,
+        genesis_hash: String,
+        spec_version: u32,
+        transaction_version: u32,
+        label: Option<String>,
+    ) -> Result<Saturn, SaturnError> {
+        utils::set_panic_hook();
+
+        let metadata = Metadata::decode(&mut &metadata_bytes[..])
+            .map_err(|e| SaturnError::invalid_input(format!("Failed to decode metadata: {}", e)))?;
+
+        let runtime_version = RuntimeVersion {
+            spec_version,
+            transaction_version,
+            other: Default::default(),
+        };
+
+        let api = OnlineClient::<PolkadotConfig>::from_rpc_client_with(
+            parse_hash(&genesis_hash)?,
+            runtime_version,
+            metadata,
+            std::sync::Arc::new(offline_rpc::OfflineRpcClient),
+        )?;
+
+        Ok(Self {
+            api,
+            url: None,
+            online: false,
+            label,
+            timeout_ms: None,
+            default_mortality: None,
+            default_tip: None,
+        })
+    }
+
+    /// Asks the node for `{ chain_name, spec_version, genesis_hash, connected }`, e.g. to check
+    /// the endpoint is a Tinkernet node before using it. Fails with a `Network` error when the
+    /// node doesn't answer, within `timeout_ms` when the client has one.
+    #[wasm_bindgen]
+    pub async fn ping(&self) -> Result<JsValue, SaturnError> {
+        let rpc = self.api.rpc();
+
+        let (chain_name, runtime_version) = timeout::with_timeout(
+            self.timeout_ms,
+            "Ping",
+            futures::future::try_join(rpc.system_chain(), rpc.runtime_version(None)),
+        )
+        .await?
+        .map_err(|e| SaturnError::network(e.to_string()))?;
+
+        let info = PingInfo {
+            chain_name,
+            spec_version: runtime_version.spec_version,
+            genesis_hash: js_signer::to_hex(self.api.genesis_hash()),
+            connected: true,
+        };
+
+        serde_wasm_bindgen::to_value(&info).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// The node's `{ token_symbol, token_decimals, ss58_prefix, tokens }`, for formatting raw
+    /// balances like "12500000000000" as "12.5 TNKR". Multi-token chains report every token in
+    /// `tokens`, with the first, native, one also in `token_symbol` and `token_decimals`.
+    #[wasm_bindgen]
+    pub async fn chain_properties(&self) -> Result<JsValue, SaturnError> {
+        let properties = self.api.rpc().system_properties().await?;
+
+        // Properties are single values on single-token chains and arrays on multi-token ones.
+        let list = |key: &str| match properties.get(key) {
+            Some(serde_json::Value::Array(values)) => values.clone(),
+            Some(value) => vec![value.clone()],
+            None => Vec::new(),
+        };
+
+        let tokens = list("tokenSymbol")
+            .iter()
+            .zip(list("tokenDecimals").iter())
+            .filter_map(|(symbol, decimals)| {
+                Some(TokenProperties {
+                    symbol: symbol.as_str()?.to_string(),
+                    decimals: decimals.as_u64()? as u32,
+                })
+            })
+            .collect::<Vec<TokenProperties>>();
+
+        let ss58_prefix = match properties
+            .get("ss58Format")
+            .and_then(|prefix| prefix.as_u64())
+        {
+            Some(prefix) => Some(prefix as u16),
+            None => self.ss58_prefix().ok(),
+        };
+
+        let chain_properties = ChainProperties {
+            token_symbol: tokens.first().map(|token| token.symbol.clone()),
+            token_decimals: tokens.first().map(|token| token.decimals),
+            ss58_prefix,
+            tokens,
+        };
+
+        serde_wasm_bindgen::to_value(&chain_properties)
+            .map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// Connects a fresh client to the URL this instance was created with, also fetching the
+    /// node's current metadata. Requests already reconnect by themselves when the connection
+    /// drops, so this is only needed to start over, e.g. after a runtime upgrade. `Call`s built
+    /// before keep using the old client, so rebuild them.
+    #[wasm_bindgen]
+    pub async fn reconnect(&mut self) -> Result<(), SaturnError> {
+        if !self.online {
+            return Err(SaturnError::invalid_input(String::from(
+                "Offline clients have no node to reconnect to",
+            )));
+        }
+
+        let Some(url) = &self.url else {
+            return Err(SaturnError::invalid_input(String::from(
+                "Clients built from an RPC provider reconnect through the provider",
+            )));
+        };
+
+        self.api = timeout::with_timeout(
+            self.timeout_ms,
+            "Connecting",
+            reconnecting_rpc::online_client(url),
+        )
+        .await??;
+
+        Ok(())
+    }
+
+    /// Like `new`, but fails with an `IncompatibleChain` error when the node lacks a pallet or
+    /// call the SDK builds on. Only the shape is checked, not the spec version, so runtime
+    /// upgrades that keep these calls stay accepted.
+    #[wasm_bindgen]
+    pub async fn new_checked(
+        url: String,
+        label: Option<String>,
+        timeout_ms: Option<u32>,
+    ) -> Result<Saturn, SaturnError> {
+        let saturn = Self::new(url, label, timeout_ms).await?;
+
+        for pallet in REQUIRED_PALLETS {
+            saturn
+                .ensure_pallet(pallet)
+                .map_err(|e| SaturnError::incompatible_chain(e.inner()))?;
+        }
+
+        let metadata = saturn.api.metadata();
+        for (pallet, call) in REQUIRED_CALLS {
+            if metadata
+                .pallet_by_name(pallet)
+                .and_then(|metadata| metadata.call_variant_by_name(call))
+                .is_none()
+            {
+                return Err(SaturnError::incompatible_chain(format!(
+                    "{}.{} call not found in metadata",
+                    pallet, call
+                )));
+            }
+        }
+
+        Ok(saturn)
+    }
+
+    #[wasm_bindgen]
+    pub fn create_core(
+        &self,
+        metadata: String,
+        minimum_support: u32,
+        required_approval: u32,
+        fee_asset: &JsFeeAsset,
+    ) -> Result<Call, SaturnError> {
+        self.ensure_metadata_fits(&metadata)?;
+
+        let minimum_support = parse_perbill(minimum_support)?;
+        let required_approval = parse_perbill(required_approval)?;
+
+        let dcd = subxt::dynamic::tx(
+            "INV4",
+            "create_core",
+            vec![
+                ("metadata", Value::from_bytes(metadata.as_bytes().to_vec())),
+                (
+                    "minimum_support",
+                    Value::unnamed_composite([Value::u128(minimum_support.deconstruct() as u128)]),
+                ),
+                (
+                    "required_approval",
+                    Value::unnamed_composite([
+                        Value::u128(required_approval.deconstruct() as u128),
+                    ]),
+                ),
+                ("creation_fee_asset", fee_asset.to_value()),
+            ],
+        );
+
+        Ok(self.new_call(dcd))
+    }
+
+    /// The account's balance of the core's voting token as `{ free, reserved, frozen }`, with
+    /// amounts as decimal strings. Accounts without a balance return zeros.
+    ///
+    /// `at_block` optionally pins the read to a historical block hash instead of the latest block.
+    #[wasm_bindgen]
+    pub async fn get_voting_balance(
+        &self,
+        core_id: u32,
+        account: String,
+        at_block: Option<String>,
+    ) -> Result<JsValue, SaturnError> {
+        let account_id = AccountId32::from_str(&account)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let storage_query = tinkernet::storage()
+            .core_assets()
+            .accounts(account_id, core_id);
+
+        let result = self
+            .storage_at(at_block)
+            .await?
+            .fetch_or_default(&storage_query)
+            .await?;
+
+        let balance = VotingBalance {
+            free: result.free.to_string(),
+            reserved: result.reserved.to_string(),
+            frozen: result.frozen.to_string(),
+        };
+
+        serde_wasm_bindgen::to_value(&balance).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// Reads the account's reserved native balance and breaks it down by reserve identifier.
+    ///
+    /// INV4 takes core deposits through anonymous reserves, so they only show up in
+    /// `total_reserved`. `by_core` stays empty unless the runtime starts using named reserves,
+    /// and whenever it's empty `note` says why.
+    #[wasm_bindgen]
+    pub async fn reserved_for_cores(&self, account: String) -> Result<JsValue, SaturnError> {
+        let account_id = AccountId32::from_str(&account)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let storage = self.storage_at(None).await?;
+
+        let account_info = storage
+            .fetch_or_default(&tinkernet::storage().system().account(&account_id))
+            .await?;
+
+        let reserves = storage
+            .fetch_or_default(&tinkernet::storage().balances().reserves(&account_id))
+            .await?;
+
+        let named_reserves: Vec<NamedReserve> = reserves
+            .0
+            .iter()
+            .map(|reserve| NamedReserve {
+                id: display_bytes(&reserve.id),
+                amount: reserve.amount.to_string(),
+            })
+            .collect();
+
+        let by_core: Vec<CoreReserve> = Vec::new();
+
+        let note = by_core.is_empty().then(|| {
+            String::from(
+                "INV4 reserves core deposits without a reserve identifier, so the reserved balance can't be attributed to specific cores",
+            )
+        });
+
+        let result = ReservedBalances {
+            total_reserved: account_info.data.reserved.to_string(),
+            named_reserves,
+            by_core,
+            note,
+        };
+
+        serde_wasm_bindgen::to_value(&result).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// SS58 address of the account the core controls its assets from, derived like the runtime
+    /// does, without any RPC round trips.
+    #[wasm_bindgen]
+    pub fn get_core_account(&self, core_id: u32) -> Result<String, SaturnError> {
+        Ok(self.address(&self.derive_core_account(core_id)?))
+    }
+
+    /// Derives the accounts of several cores at once, without any RPC round trips.
+    #[wasm_bindgen]
+    pub fn derive_core_accounts(&self, core_ids: Vec<u32>) -> Result<JsValue, SaturnError> {
+        let accounts = core_ids
+            .into_iter()
+            .map(|core_id| {
+                self.derive_core_account(core_id)
+                    .map(|account| CoreAccount {
+                        core_id,
+                        address: self.address(&account),
+                    })
+            })
+            .collect::<Result<Vec<CoreAccount>, SaturnError>>()?;
+
+        serde_wasm_bindgen::to_value(&accounts).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// What the core's account holds, as `[{ asset, free, reserved }]`: its native TNKR balance
+    /// and every `Tokens` balance, with `asset` the currency id for tokens other than KSM.
+    /// Runtimes without the `Tokens` pallet only report the native balance.
+    ///
+    /// `at_block` optionally pins the reads to a historical block hash, as in `get_voting_balance`.
+    #[wasm_bindgen]
+    pub async fn get_core_assets(
+        &self,
+        core_id: u32,
+        at_block: Option<String>,
+    ) -> Result<JsValue, SaturnError> {
+        let account = self.derive_core_account(core_id)?;
+
+        let storage = self.storage_at(at_block).await?;
+
+        let native = storage
+            .fetch_or_default(&tinkernet::storage().system().account(&account))
+            .await?
+            .data;
+
+        let mut assets = vec![CoreAsset {
+            asset: String::from("TNKR"),
+            free: native.free.to_string(),
+            reserved: native.reserved.to_string(),
+        }];
+
+        if self.ensure_pallet("Tokens").is_ok() {
+            let ksm_asset_id = self
+                .api
+                .constants()
+                .at(&tinkernet::constants().inv4().ksm_asset_id())?;
+
+            let prefix = map_prefix("Tokens", "Accounts", &account);
+
+            for key in keys_with_prefix(&storage, &prefix).await? {
+                let currency_id = key_suffix_u32(&key, "Tokens.Accounts")?;
+
+                let balance = storage
+                    .fetch_or_default(
+                        &tinkernet::storage()
+                            .tokens()
+                            .accounts(&account, currency_id),
+                    )
+                    .await?;
+
+                assets.push(CoreAsset {
+                    asset: if currency_id == ksm_asset_id {
+                        String::from("KSM")
+                    } else {
+                        currency_id.to_string()
+                    },
+                    free: balance.free.to_string(),
+                    reserved: balance.reserved.to_string(),
+                });
+            }
+        }
+
+        serde_wasm_bindgen::to_value(&assets).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// Scans the events of blocks `from_block..=to_block` and returns a timeline of the account's
+    /// proposals, votes, minted tokens and OCIF stakes.
+    ///
+    /// The range is capped at `MAX_EVENT_SCAN_BLOCKS` blocks. `progress_callback`, if given, is
+    /// called with `(scanned_blocks, total_blocks)` after each block.
+    #[wasm_bindgen]
+    pub async fn account_governance_activity(
+        &self,
+        account: String,
+        from_block: u32,
+        to_block: u32,
+        progress_callback: Option<js_sys::Function>,
+    ) -> Result<JsValue, SaturnError> {
+        use tinkernet::{inv4::events as inv4_events, ocif_staking::events as ocif_events};
+
+        let account_id = AccountId32::from_str(&account)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        if to_block < from_block {
+            return Err(SaturnError::invalid_input(String::from(
+                "to_block must not be lower than from_block",
+            )));
+        }
+
+        let total_blocks = to_block - from_block + 1;
+        if total_blocks > MAX_EVENT_SCAN_BLOCKS {
+            return Err(SaturnError::invalid_input(format!(
+                "Block range too large, at most {} blocks can be scanned at once",
+                MAX_EVENT_SCAN_BLOCKS
+            )));
+        }
+
+        let mut activity = Vec::new();
+
+        for block_number in from_block..=to_block {
+            let block_hash = self.block_hash_at(block_number).await?;
+
+            let events = self.api.blocks().at(block_hash).await?.events().await?;
+
+            for event in events.iter() {
+                let event = event?;
+
+                let entry = if let Some(started) =
+                    event.as_event::<inv4_events::MultisigVoteStarted>()?
+                {
+                    (started.voter == account_id).then(|| GovernanceActivity {
+                        block_number,
+                        kind: "proposal",
+                        core_id: started.core_id,
+                        call_hash: Some(js_signer::to_hex(started.call_hash)),
+                        aye: None,
+                        amount: None,
+                    })
+                } else if let Some(added) = event.as_event::<inv4_events::MultisigVoteAdded>()? {
+                    use tinkernet::runtime_types::pallet_inv4::voting::Vote;
+
+                    let (aye, amount) = match added.votes_added {
+                        Vote::Aye(amount) => (true, amount),
+                        Vote::Nay(amount) => (false, amount),
+                    };
+
+                    (added.voter == account_id).then(|| GovernanceActivity {
+                        block_number,
+                        kind: "vote",
+                        core_id: added.core_id,
+                        call_hash: Some(js_signer::to_hex(added.call_hash)),
+                        aye: Some(aye),
+                        amount: Some(amount.to_string()),
+                    })
+                } else if let Some(minted) = event.as_event::<inv4_events::Minted>()? {
+                    (minted.target == account_id).then(|| GovernanceActivity {
+                        block_number,
+                        kind: "mint_received",
+                        core_id: minted.core_id,
+                        call_hash: None,
+                        aye: None,
+                        amount: Some(minted.amount.to_string()),
+                    })
+                } else if let Some(staked) = event.as_event::<ocif_events::Staked>()? {
+                    (staked.staker == account_id).then(|| GovernanceActivity {
+                        block_number,
+                        kind: "stake",
+                        core_id: staked.core,
+                        call_hash: None,
+                        aye: None,
+                        amount: Some(staked.amount.to_string()),
+                    })
+                } else {
+                    None
+                };
+
+                activity.extend(entry);
+            }
+
+            if let Some(callback) = &progress_callback {
+                // A throwing progress callback shouldn't abort the scan.
+                let _ = callback.call2(
+                    &JsValue::null(),
+                    &JsValue::from(block_number - from_block + 1),
+                    &JsValue::from(total_blocks),
+                );
+            }
+        }
+
+        serde_wasm_bindgen::to_value(&activity).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// For a vote on a core with frozen tokens, where the member set can't change, returns the
+    /// range of possible outcomes and which undecided members' votes are decisive.
+    ///
+    /// A member is decisive when the proposal can still pass, but fails if that member votes nay
+    /// while every other undecided member votes aye.
+    #[wasm_bindgen]
+    pub async fn frozen_vote_outlook(
+        &self,
+        core_id: u32,
+        call_hash: String,
+    ) -> Result<JsValue, SaturnError> {
+        let call_hash = parse_hash(&call_hash)?;
+
+        let storage = self.storage_at(None).await?;
+
+        let core = storage
+            .fetch(&tinkernet::storage().inv4().core_storage(core_id))
+            .await?
+            .ok_or(SaturnError::invalid_input(format!(
+                "Core {} not found",
+                core_id
+            )))?;
+
+        if !core.frozen_tokens {
+            return Err(SaturnError::invalid_input(format!(
+                "Core {} doesn't have frozen tokens",
+                core_id
+            )));
+        }
+
+        let proposal = storage
+            .fetch(&tinkernet::storage().inv4().multisig(core_id, call_hash))
+            .await?
+            .ok_or(SaturnError::invalid_input(format!(
+                "Proposal {} not found in core {}",
+                js_signer::to_hex(call_hash),
+                core_id
+            )))?;
+
+        let total_issuance = storage
+            .fetch_or_default(&tinkernet::storage().core_assets().total_issuance(core_id))
+            .await?;
+
+        let ayes = proposal.tally.ayes;
+        let nays = proposal.tally.nays;
+
+        let mut undecided = Vec::new();
+        for member in self.core_member_accounts(&storage, core_id).await? {
+            if proposal
+                .tally
+                .records
+                .0
+                .iter()
+                .any(|(voter, _)| *voter == member)
+            {
+                continue;
+            }
+
+            let balance = storage
+                .fetch_or_default(
+                    &tinkernet::storage()
+                        .core_assets()
+                        .accounts(&member, core_id),
+                )
+                .await?;
+
+            if balance.free > 0 {
+                undecided.push((member, balance.free));
+            }
+        }
+
+        let undecided_weight: u128 = undecided.iter().map(|(_, weight)| weight).sum();
+
+        let minimum_support = core.minimum_support.0;
+        let required_approval = core.required_approval.0;
+        let passes = |ayes: u128, nays: u128| {
+            tally_passes(
+                ayes,
+                nays,
+                total_issuance,
+                minimum_support,
+                required_approval,
+            )
+        };
+
+        let can_pass = passes(ayes.saturating_add(undecided_weight), nays);
+
+        let decisive_members = undecided
+            .iter()
+            .filter(|(_, weight)| {
+                can_pass
+                    && !passes(
+                        ayes.saturating_add(undecided_weight - weight),
+                        nays.saturating_add(*weight),
+                    )
+            })
+            .map(|(member, _)| self.address(member))
+            .collect();
+
+        let outlook = VoteOutlook {
+            ayes: ayes.to_string(),
+            nays: nays.to_string(),
+            undecided: undecided_weight.to_string(),
+            passes_now: passes(ayes, nays),
+            can_pass,
+            guaranteed_to_pass: passes(ayes, nays.saturating_add(undecided_weight)),
+            decisive_members,
+        };
+
+        serde_wasm_bindgen::to_value(&outlook).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// Decodes hex encoded call data, such as a proposal's stored call, into
+    /// `{ pallet, call, args }` using the current runtime's metadata. See
+    /// `decode_call_with_version` for calls encoded before a runtime upgrade.
+    #[wasm_bindgen]
+    pub fn decode_call(&self, call_data_hex: String) -> Result<JsValue, SaturnError> {
+        let call_data = parse_call_data(&call_data_hex)?;
+
+        let decoded = call_decoding::decode_call(&self.api.metadata(), &call_data)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        serde_wasm_bindgen::to_value(&decoded.describe())
+            .map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// Decodes hex encoded call data using the metadata of the runtime it was encoded under, so
+    /// proposals created before a runtime upgrade can still be displayed.
+    ///
+    /// The current metadata is used when `spec_version` is the running one. Otherwise the
+    /// finalized chain is binary searched for a block running `spec_version` and that block's
+    /// metadata is fetched from the node, which requires an archive node for old versions.
+    #[wasm_bindgen]
+    pub async fn decode_call_with_version(
+        &self,
+        call_hex: String,
+        spec_version: u32,
+    ) -> Result<JsValue, SaturnError> {
+        let call_data = parse_call_data(&call_hex)?;
+
+        let metadata = if spec_version == self.api.runtime_version().spec_version {
+            self.api.metadata()
+        } else {
+            let block_hash = self.find_block_with_spec_version(spec_version).await?;
+
+            self.api.rpc().metadata_legacy(Some(block_hash)).await?
+        };
+
+        let decoded = call_decoding::decode_call(&metadata, &call_data)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        serde_wasm_bindgen::to_value(&decoded.describe())
+            .map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// The minimum amount that can be staked on a core through OCIF, as a decimal string.
+    #[wasm_bindgen]
+    pub fn min_stake(&self) -> Result<String, SaturnError> {
+        self.ocif_min_stake().map(|amount| amount.to_string())
+    }
+
+    /// Stakes `amount` on a core through OCIF, rejecting amounts below the minimum stake before
+    /// any extrinsic is built.
+    #[wasm_bindgen]
+    pub fn ocif_stake(&self, core_id: u32, amount: String) -> Result<Call, SaturnError> {
+        let amount = parse_amount(&amount)?;
+        let min_stake = self.ocif_min_stake()?;
+
+        if amount < min_stake {
+            return Err(SaturnError::invalid_input(format!(
+                "{} is below the minimum stake of {}",
+                amount, min_stake
+            )));
+        }
+
+        let call = subxt::dynamic::tx(
+            "OcifStaking",
+            "stake",
+            vec![
+                ("core_id", Value::u128(core_id as u128)),
+                ("value", Value::u128(amount)),
+            ],
+        );
+
+        Ok(self.new_call(call))
+    }
+
+    /// Builds `OcifStaking.unregister_core`, which has to be dispatched by the core itself,
+    /// so it's usually wrapped in `operate_multisig`; see `propose_ocif_unregister`.
+    ///
+    /// Stakers aren't refunded by unregistering, they still have to unstake and withdraw their
+    /// own funds, so it's worth letting them know before the core opts out.
+    #[wasm_bindgen]
+    pub async fn ocif_unregister_core(&self, core_id: u32) -> Result<Call, SaturnError> {
+        self.ensure_pallet("OcifStaking")?;
+
+        self.storage_at(None)
+            .await?
+            .fetch(&tinkernet::storage().ocif_staking().registered_core(core_id))
+            .await?
+            .ok_or(SaturnError::invalid_input(format!(
+                "Core {} is not registered for OCIF staking",
+                core_id
+            )))?;
+
+        let call = subxt::dynamic::tx(
+            "OcifStaking",
+            "unregister_core",
+            Vec::<(&str, Value)>::new(),
+        );
+
+        Ok(self.new_call(call))
+    }
+
+    /// Combines `calls` into a single `Utility.batch_all` call, signed and submitted once.
+    /// If any of them fails the whole batch is reverted, and the submission's `error` names the
+    /// failed call's error but not which call it was.
+    #[wasm_bindgen]
+    pub fn batch(&self, calls: Vec<Call>) -> Result<Call, SaturnError> {
+        if calls.is_empty() {
+            return Err(SaturnError::invalid_input(String::from(
+                "Can't batch an empty list of calls",
+            )));
+        }
+
+        if calls
+            .iter()
+            .any(|call| call.api.genesis_hash() != self.api.genesis_hash())
+        {
+            return Err(SaturnError::invalid_input(String::from(
+                "Can't batch calls built for a different chain",
+            )));
+        }
+
+        let calls = calls.into_iter().map(|call| call.call.into_value());
+
+        Ok(self.new_call(subxt::dynamic::tx(
+            "Utility",
+            "batch_all",
+            vec![("calls", Value::unnamed_composite(calls))],
+        )))
+    }
+
+    /// Proposes `call` to the core's members through `INV4.operate_multisig`, with `metadata`
+    /// optionally describing the proposal.
+    ///
+    /// If the submitter's own voting weight already meets the core's support and approval
+    /// thresholds the call executes right away, emitting `MultisigExecuted`. Otherwise it's
+    /// stored as a pending proposal, emitting `MultisigVoteStarted`, and executes once votes
+    /// push it over the thresholds.
+    ///
+    /// Proposing another `operate_multisig` is rejected, as it would run with the core's origin,
+    /// which holds no voting tokens, and fail after fees are paid. Pass `allow_nested` to skip
+    /// the check.
+    #[wasm_bindgen]
+    pub fn operate_multisig(
+        &self,
+        core_id: u32,
+        metadata: Option<String>,
+        fee_asset: &JsFeeAsset,
+        call: &Call,
+        allow_nested: Option<bool>,
+    ) -> Result<Call, SaturnError> {
+        if let Some(metadata) = &metadata {
+            self.ensure_metadata_fits(metadata)?;
+        }
+
+        Ok(self.new_call(multisig_payload(
+            &self.api,
+            core_id,
+            metadata,
+            fee_asset,
+            call,
+            allow_nested.unwrap_or(false),
+        )?))
+    }
+
+    /// Proposes `calls` as one `Utility.batch_all` through `operate_multisig`, so members vote
+    /// on them together and they execute all-or-nothing once the proposal passes.
+    /// `allow_nested` works as in `operate_multisig`, for each of the calls.
+    #[wasm_bindgen]
+    pub fn operate_multisig_batch(
+        &self,
+        core_id: u32,
+        metadata: Option<String>,
+        fee_asset: &JsFeeAsset,
+        calls: Vec<Call>,
+        allow_nested: Option<bool>,
+    ) -> Result<Call, SaturnError> {
+        if !allow_nested.unwrap_or(false) {
+            calls.iter().try_for_each(ensure_not_multisig)?;
+        }
+
+        let batch = self.batch(calls)?;
+
+        self.operate_multisig(core_id, metadata, fee_asset, &batch, allow_nested)
+    }
+
+    /// Votes on a pending proposal of the core. When the vote pushes the proposal over the
+    /// core's thresholds it executes in the same extrinsic, emitting `MultisigExecuted` instead
+    /// of `MultisigVoteAdded`. That event and those of the executed call come back in the
+    /// vote's `SubmissionResult.events`.
+    #[wasm_bindgen]
+    pub fn vote_multisig(
+        &self,
+        core_id: u32,
+        call_hash: String,
+        aye: bool,
+    ) -> Result<Call, SaturnError> {
+        let call_hash = parse_hash(&call_hash)?;
+
+        let call = subxt::dynamic::tx(
+            "INV4",
+            "vote_multisig",
+            vec![
+                ("core_id", Value::u128(core_id as u128)),
+                ("call_hash", Value::from_bytes(call_hash.0)),
+                ("aye", Value::bool(aye)),
+            ],
+        );
+
+        Ok(self.new_call(call))
+    }
+
+    /// Withdraws the submitter's vote on a pending proposal of the core. Withdrawing from a
+    /// proposal the account never voted on fails with the pallet's error, e.g.
+    /// `INV4.NotAVoter`, when submitted.
+    #[wasm_bindgen]
+    pub fn withdraw_vote_multisig(
+        &self,
+        core_id: u32,
+        call_hash: String,
+    ) -> Result<Call, SaturnError> {
+        let call_hash = parse_hash(&call_hash)?;
+
+        let call = subxt::dynamic::tx(
+            "INV4",
+            "withdraw_vote_multisig",
+            vec![
+                ("core_id", Value::u128(core_id as u128)),
+                ("call_hash", Value::from_bytes(call_hash.0)),
+            ],
+        );
+
+        Ok(self.new_call(call))
+    }
+
+    /// Proposes cancelling one of the core's pending proposals. `INV4.cancel_multisig_proposal`
+    /// has to be dispatched by the core, so it's wrapped in `operate_multisig` and goes through
+    /// a vote like any other proposal, unless the submitter's weight alone passes it. Once
+    /// executed the events include `INV4.MultisigCanceled`.
+    #[wasm_bindgen]
+    pub fn cancel_multisig_proposal(
+        &self,
+        core_id: u32,
+        call_hash: String,
+        fee_asset: &JsFeeAsset,
+    ) -> Result<Call, SaturnError> {
+        let call_hash = parse_hash(&call_hash)?;
+
+        let cancel = self.new_call(subxt::dynamic::tx(
+            "INV4",
+            "cancel_multisig_proposal",
+            vec![("call_hash", Value::from_bytes(call_hash.0))],
+        ));
+
+        Ok(self.new_call(multisig_payload(
+            &self.api, core_id, None, fee_asset, &cancel, false,
+        )?))
+    }
+
+    /// Proposes changing the core's voting thresholds, given as Perbill parts like in
+    /// `create_core`, and whether its tokens are frozen. Omitted parameters stay unchanged.
+    /// `INV4.set_parameters` has to be dispatched by the core, so it's wrapped in
+    /// `operate_multisig`.
+    #[wasm_bindgen]
+    pub fn set_parameters(
+        &self,
+        core_id: u32,
+        minimum_support: Option<u32>,
+        required_approval: Option<u32>,
+        frozen_tokens: Option<bool>,
+        fee_asset: &JsFeeAsset,
+    ) -> Result<Call, SaturnError> {
+        let perbill_value = |parts: u32| {
+            parse_perbill(parts).map(|perbill| {
+                Value::unnamed_composite([Value::u128(perbill.deconstruct() as u128)])
+            })
+        };
+
+        let minimum_support = minimum_support.map(perbill_value).transpose()?;
+        let required_approval = required_approval.map(perbill_value).transpose()?;
+
+        let set_parameters = self.new_call(subxt::dynamic::tx(
+            "INV4",
+            "set_parameters",
+            vec![
+                ("metadata", option_value(None)),
+                ("minimum_support", option_value(minimum_support)),
+                ("required_approval", option_value(required_approval)),
+                (
+                    "frozen_tokens",
+                    option_value(frozen_tokens.map(Value::bool)),
+                ),
+            ],
+        ));
+
+        Ok(self.new_call(multisig_payload(
+            &self.api,
+            core_id,
+            None,
+            fee_asset,
+            &set_parameters,
+            false,
+        )?))
+    }
+
+    /// Re-encodes an SS58 address of any network in the chain's own format, e.g. a generic
+    /// `5...` address as the `i...` Tinkernet address explorers show.
+    #[wasm_bindgen]
+    pub fn format_address(&self, account: String) -> Result<String, SaturnError> {
+        let account_id = AccountId32::from_str(&account)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        Ok(ss58::encode(&account_id, self.ss58_prefix()?))
+    }
+
+    /// Most bytes of metadata a core or proposal can carry, the runtime's `INV4.MaxMetadata`.
+    #[wasm_bindgen]
+    pub fn max_metadata_length(&self) -> Result<u32, SaturnError> {
+        self.api
+            .constants()
+            .at(&tinkernet::constants().inv4().max_metadata())
+            .map_err(SaturnError::from)
+    }
+
+    /// Proposes replacing the core's metadata. INV4 updates it through `set_parameters`, which
+    /// is wrapped in `operate_multisig` like in `set_parameters`. Metadata longer than the
+    /// runtime's `MaxMetadata` is rejected up front.
+    #[wasm_bindgen]
+    pub fn set_core_metadata(
+        &self,
+        core_id: u32,
+        metadata: String,
+        fee_asset: &JsFeeAsset,
+    ) -> Result<Call, SaturnError> {
+        self.ensure_metadata_fits(&metadata)?;
+
+        let set_parameters = self.new_call(subxt::dynamic::tx(
+            "INV4",
+            "set_parameters",
+            vec![
+                (
+                    "metadata",
+                    option_value(Some(Value::from_bytes(metadata.as_bytes().to_vec()))),
+                ),
+                ("minimum_support", option_value(None)),
+                ("required_approval", option_value(None)),
+                ("frozen_tokens", option_value(None)),
+            ],
+        ));
+
+        Ok(self.new_call(multisig_payload(
+            &self.api,
+            core_id,
+            None,
+            fee_asset,
+            &set_parameters,
+            false,
+        )?))
+    }
+
+    /// Proposes minting `amount` of the core's voting token to `target`, wrapped in
+    /// `operate_multisig` since `INV4.token_mint` has to be dispatched by the core.
+    #[wasm_bindgen]
+    pub fn token_mint(
+        &self,
+        core_id: u32,
+        amount: String,
+        target: String,
+        fee_asset: &JsFeeAsset,
+    ) -> Result<Call, SaturnError> {
+        let amount = parse_amount(&amount)?;
+        let target = AccountId32::from_str(&target)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let mint = self.new_call(subxt::dynamic::tx(
+            "INV4",
+            "token_mint",
+            vec![
+                ("amount", Value::u128(amount)),
+                ("target", Value::from_bytes(target.0)),
+            ],
+        ));
+
+        Ok(self.new_call(multisig_payload(
+            &self.api, core_id, None, fee_asset, &mint, false,
+        )?))
+    }
+
+    /// Transfers `amount` of the core's voting token from the signer to `target` through
+    /// `CoreAssets.transfer`. Cores with frozen tokens reject transfers when submitted.
+    #[wasm_bindgen]
+    pub fn token_transfer(
+        &self,
+        core_id: u32,
+        amount: String,
+        target: String,
+    ) -> Result<Call, SaturnError> {
+        let amount = parse_amount(&amount)?;
+        if amount == 0 {
+            return Err(SaturnError::invalid_input(String::from(
+                "Transfer amount must be greater than zero",
+            )));
+        }
+
+        let target = AccountId32::from_str(&target)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let call = subxt::dynamic::tx(
+            "CoreAssets",
+            "transfer",
+            vec![
+                (
+                    "dest",
+                    Value::unnamed_variant("Id", [Value::from_bytes(target.0)]),
+                ),
+                ("currency_id", Value::u128(core_id as u128)),
+                ("amount", Value::u128(amount)),
+            ],
+        );
+
+        Ok(self.new_call(call))
+    }
+
+    /// Proposes dispatching `call_data_hex`, a call encoded for the destination chain, from the
+    /// core's account on parachain `destination_para_id` through `Rings.send_call`, wrapped in
+    /// `operate_multisig` like `token_mint`.
+    ///
+    /// Rings only reaches the chains in `RINGS_DESTINATIONS`. Nothing about the remote call is
+    /// estimated: `weight` is the weight bought for its `Transact`, and `remote_fee` is the
+    /// decimal amount of KSM the core's account there pays for it, so both have to cover the
+    /// call or it fails on the destination after this call succeeds.
+    #[wasm_bindgen]
+    pub fn send_xcm_call(
+        &self,
+        core_id: u32,
+        destination_para_id: u32,
+        call_data_hex: String,
+        weight: XcmWeight,
+        remote_fee: String,
+        fee_asset: &JsFeeAsset,
+    ) -> Result<Call, SaturnError> {
+        let Some((_, destination)) = RINGS_DESTINATIONS
+            .iter()
+            .find(|(para_id, _)| *para_id == destination_para_id)
+        else {
+            return Err(SaturnError::invalid_input(format!(
+                "Parachain {} is not reachable through Rings, expected one of {}",
+                destination_para_id,
+                RINGS_DESTINATIONS.map(|(para_id, chain)| format!("{} ({})", para_id, chain)).join(", ")
+            )));
+        };
+
+        let call_data = hex::decode(call_data_hex.trim_start_matches("0x"))
+            .map_err(|e| SaturnError::invalid_input(format!("Invalid call data: {}", e)))?;
+        if call_data.is_empty() {
+            return Err(SaturnError::invalid_input(String::from(
+                "Call data is empty",
+            )));
+        }
+
+        let max_call_length = self
+            .api
+            .constants()
+            .at(&tinkernet::constants().rings().max_xcm_call_length())?;
+
+        if call_data.len() > max_call_length as usize {
+            return Err(SaturnError::invalid_input(format!(
+                "Call is {} bytes, larger than the maximum of {}",
+                call_data.len(),
+                max_call_length
+            )));
+        }
+
+        let remote_fee = parse_amount(&remote_fee)?;
+
+        let send_call = self.new_call(subxt::dynamic::tx(
+            "Rings",
+            "send_call",
+            vec![
+                ("destination", Value::unnamed_variant(*destination, [])),
+                (
+                    "weight",
+                    Value::named_composite([
+                        ("ref_time", Value::u128(weight.ref_time as u128)),
+                        ("proof_size", Value::u128(weight.proof_size as u128)),
+                    ]),
+                ),
+                (
+                    "fee_asset",
+                    Value::unnamed_variant(*destination, [Value::unnamed_variant("KSM", [])]),
+                ),
+                ("fee", Value::u128(remote_fee)),
+                ("call", Value::from_bytes(call_data)),
+            ],
+        ));
+
+        Ok(self.new_call(multisig_payload(
+            &self.api, core_id, None, fee_asset, &send_call, false,
+        )?))
+    }
+
+    /// Proposes burning `amount` of the core's voting token from `target`, wrapped in
+    /// `operate_multisig` like `token_mint`.
+    #[wasm_bindgen]
+    pub fn token_burn(
+        &self,
+        core_id: u32,
+        amount: String,
+        target: String,
+        fee_asset: &JsFeeAsset,
+    ) -> Result<Call, SaturnError> {
+        let amount = parse_amount(&amount)?;
+        let target = AccountId32::from_str(&target)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let burn = self.new_call(subxt::dynamic::tx(
+            "INV4",
+            "token_burn",
+            vec![
+                ("amount", Value::u128(amount)),
+                ("target", Value::from_bytes(target.0)),
+            ],
+        ));
+
+        Ok(self.new_call(multisig_payload(
+            &self.api, core_id, None, fee_asset, &burn, false,
+        )?))
+    }
+
+    /// Proposes unregistering the core from OCIF staking to the core's members.
+    #[wasm_bindgen]
+    pub async fn propose_ocif_unregister(
+        &self,
+        core_id: u32,
+        fee_asset: &JsFeeAsset,
+    ) -> Result<Call, SaturnError> {
+        let unregister = self.ocif_unregister_core(core_id).await?;
+
+        Ok(self.new_call(multisig_payload(
+            &self.api,
+            core_id,
+            None,
+            fee_asset,
+            &unregister,
+            false,
+        )?))
+    }
+
+    /// Proposes registering the core for OCIF staking with the given metadata, wrapped in
+    /// `operate_multisig` since registration has to be dispatched by the core.
+    ///
+    /// Each field is checked against its runtime length bound. Once executed, the
+    /// `RegisterDeposit` is reserved from the core account.
+    #[wasm_bindgen]
+    pub fn ocif_register_core(
+        &self,
+        core_id: u32,
+        name: String,
+        description: String,
+        image_url: String,
+        fee_asset: &JsFeeAsset,
+    ) -> Result<Call, SaturnError> {
+        self.ensure_pallet("OcifStaking")?;
+
+        let constants = tinkernet::constants().ocif_staking();
+        for (field, value, max_length) in [
+            ("name", &name, constants.max_name_length()),
+            (
+                "description",
+                &description,
+                constants.max_description_length(),
+            ),
+            ("image_url", &image_url, constants.max_image_url_length()),
+        ] {
+            let max_length = self.api.constants().at(&max_length)?;
+
+            if value.len() > max_length as usize {
+                return Err(SaturnError::invalid_input(format!(
+                    "OCIF {} is {} bytes long, the maximum is {}",
+                    field,
+                    value.len(),
+                    max_length
+                )));
+            }
+        }
+
+        let register = self.new_call(subxt::dynamic::tx(
+            "OcifStaking",
+            "register_core",
+            vec![
+                ("name", Value::from_bytes(name)),
+                ("description", Value::from_bytes(description)),
+                ("image", Value::from_bytes(image_url)),
+            ],
+        ));
+
+        Ok(self.new_call(multisig_payload(
+            &self.api, core_id, None, fee_asset, &register, false,
+        )?))
+    }
+
+    /// Pending core-side OCIF rewards, as `{ total, eras: [{ era, amount }] }`, so a UI knows
+    /// when claiming is worth it.
+    #[wasm_bindgen]
+    pub async fn get_core_rewards(&self, core_id: u32) -> Result<JsValue, SaturnError> {
+        let pending = self.pending_core_rewards(core_id).await?;
+
+        let rewards = CoreRewards {
+            total: pending
+                .iter()
+                .map(|(_, amount)| amount)
+                .sum::<u128>()
+                .to_string(),
+            eras: pending
+                .into_iter()
+                .map(|(era, amount)| EraReward {
+                    era,
+                    amount: amount.to_string(),
+                })
+                .collect(),
+        };
+
+        serde_wasm_bindgen::to_value(&rewards).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// Proposes claiming the core's OCIF rewards for `era`, or for the oldest unclaimed era when
+    /// omitted, wrapped in `operate_multisig`.
+    #[wasm_bindgen]
+    pub async fn ocif_claim_core_rewards(
+        &self,
+        core_id: u32,
+        era: Option<u32>,
+        fee_asset: &JsFeeAsset,
+    ) -> Result<Call, SaturnError> {
+        let era = match era {
+            Some(era) => era,
+            None => self
+                .pending_core_rewards(core_id)
+                .await?
+                .first()
+                .map(|(era, _)| *era)
+                .ok_or(SaturnError::invalid_input(format!(
+                    "Core {} has no unclaimed OCIF rewards",
+                    core_id
+                )))?,
+        };
+
+        let claim = self.new_call(subxt::dynamic::tx(
+            "OcifStaking",
+            "core_claim_rewards",
+            vec![
+                ("core_id", Value::u128(core_id as u128)),
+                ("era", Value::u128(era as u128)),
+            ],
+        ));
+
+        Ok(self.new_call(multisig_payload(
+            &self.api, core_id, None, fee_asset, &claim, false,
+        )?))
+    }
+
+    /// Address a passed proposal's inner call will be dispatched as, which is the core's
+    /// derived account.
+    #[wasm_bindgen]
+    pub fn proposal_origin(&self, core_id: u32) -> Result<String, SaturnError> {
+        self.derive_core_account(core_id)
+            .map(|account| self.address(&account))
+    }
+
+    /// Rebuilds a `Call` from the output of `Call.to_compact`.
+    #[wasm_bindgen]
+    pub fn call_from_compact(&self, compact: String) -> Result<Call, SaturnError> {
+        let call_data = URL_SAFE_NO_PAD
+            .decode(compact)
+            .map_err(|e| SaturnError::invalid_input(format!("Invalid compact call: {}", e)))?;
+
+        self.call_from_call_data(&call_data)
+    }
+
+    /// Wraps hex encoded SCALE call data, e.g. from another tool, into a `Call`. Bytes that
+    /// don't decode to exactly one call in the current metadata are rejected.
+    #[wasm_bindgen]
+    pub fn call_from_bytes(&self, call_data_hex: String) -> Result<Call, SaturnError> {
+        let call_data = hex::decode(call_data_hex.trim_start_matches("0x"))
+            .map_err(|e| SaturnError::invalid_input(format!("Invalid call data: {}", e)))?;
+
+        self.call_from_call_data(&call_data)
+    }
+
+    /// Watches the account's nonce on new best blocks and calls `callback(nonce)` whenever it
+    /// changes, e.g. after a transaction submitted elsewhere, so a local nonce tracker can resync.
+    #[wasm_bindgen]
+    pub fn subscribe_nonce(
+        &self,
+        account: String,
+        callback: js_sys::Function,
+    ) -> Result<Subscription, SaturnError> {
+        let account_id = AccountId32::from_str(&account)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let api = self.api.clone();
+        let label = self.label.clone();
+
+        Ok(subscription::spawn(async move {
+            let result = async {
+                let mut blocks = api.blocks().subscribe_best().await?;
+                let mut last_nonce = None;
+
+                while let Some(block) = blocks.next().await {
+                    let nonce = api
+                        .storage()
+                        .at(block?.hash())
+                        .fetch_or_default(&tinkernet::storage().system().account(&account_id))
+                        .await?
+                        .nonce;
+
+                    if last_nonce.map_or(false, |last_nonce| last_nonce != nonce) {
+                        let _ = callback.call1(&JsValue::null(), &JsValue::from(nonce));
+                    }
+                    last_nonce = Some(nonce);
+                }
+
+                Ok::<(), SubxtError>(())
+            }
+            .await;
+
+            if let Err(e) = result {
+                utils::console_log!(label = label.as_deref(); "rust subscribe_nonce error: {:?}", e);
+            }
+        }))
+    }
+
+    /// Calls `callback({ number, hash })` for every new finalized block. If the stream fails,
+    /// e.g. on a disconnect, `callback({ error })` is called once and the subscription ends.
+    #[wasm_bindgen]
+    pub fn subscribe_finalized_blocks(
+        &self,
+        callback: js_sys::Function,
+    ) -> Result<Subscription, SaturnError> {
+        let api = self.api.clone();
+        let label = self.label.clone();
+
+        Ok(subscription::spawn(async move {
+            let result = async {
+                let mut blocks = api.blocks().subscribe_finalized().await?;
+
+                while let Some(block) = blocks.next().await {
+                    let block = block?;
+
+                    let block = serde_wasm_bindgen::to_value(&BlockInfo {
+                        number: block.number(),
+                        hash: js_signer::to_hex(block.hash()),
+                    })
+                    .map_err(|e| SaturnError::encoding(e.to_string()))?;
+                    let _ = callback.call1(&JsValue::null(), &block);
+                }
+
+                Ok::<(), SaturnError>(())
+            }
+            .await;
+
+            if let Err(e) = result {
+                utils::console_log!(label = label.as_deref(); "rust subscribe_finalized_blocks error: {:?}", e);
+
+                if let Ok(error) =
+                    serde_wasm_bindgen::to_value(&SubscriptionError { error: e.inner() })
+                {
+                    let _ = callback.call1(&JsValue::null(), &error);
+                }
+            }
+        }))
+    }
+
+    /// Calls `callback({ block_number, block_hash, event, fields })` for every INV4 event about
+    /// the core in new finalized blocks, such as new proposals, votes and executions. Stream
+    /// failures are reported like in `subscribe_finalized_blocks`.
+    #[wasm_bindgen]
+    pub fn subscribe_core_events(
+        &self,
+        core_id: u32,
+        callback: js_sys::Function,
+    ) -> Result<Subscription, SaturnError> {
+        let api = self.api.clone();
+        let label = self.label.clone();
+
+        Ok(subscription::spawn(async move {
+            let result = async {
+                let mut blocks = api.blocks().subscribe_finalized().await?;
+
+                while let Some(block) = blocks.next().await {
+                    let block = block?;
+
+                    for event in block.events().await?.iter() {
+                        let event = event?;
+                        if event.pallet_name() != "INV4" {
+                            continue;
+                        }
+
+                        let fields = event.field_values()?;
+
+                        // Every INV4 event names the core it's about in a `core_id` field.
+                        let is_about_core = match &fields {
+                            Composite::Named(fields) => fields.iter().any(|(name, value)| {
+                                name == "core_id" && value.as_u128() == Some(core_id as u128)
+                            }),
+                            Composite::Unnamed(_) => false,
+                        };
+                        if !is_about_core {
+                            continue;
+                        }
+
+                        let core_event = serde_wasm_bindgen::to_value(&CoreEvent {
+                            block_number: block.number(),
+                            block_hash: js_signer::to_hex(block.hash()),
+                            event: event.variant_name().to_string(),
+                            fields: call_decoding::describe_fields(&fields),
+                        })
+                        .map_err(|e| SaturnError::encoding(e.to_string()))?;
+                        let _ = callback.call1(&JsValue::null(), &core_event);
+                    }
+                }
+
+                Ok::<(), SaturnError>(())
+            }
+            .await;
+
+            if let Err(e) = result {
+                utils::console_log!(label = label.as_deref(); "rust subscribe_core_events error: {:?}", e);
+
+                if let Ok(error) =
+                    serde_wasm_bindgen::to_value(&SubscriptionError { error: e.inner() })
+                {
+                    let _ = callback.call1(&JsValue::null(), &error);
+                }
+            }
+        }))
+    }
+
+    /// Watches the core's pending proposals on new best blocks and calls
+    /// `callback({ added, removed, updated })` for every block that changes them. `added` and
+    /// `updated` hold proposals shaped like `proposals_with_calls` entries, `removed` their call
+    /// hashes. The first callback lists every pending proposal as added.
+    #[wasm_bindgen]
+    pub fn subscribe_proposal_changes(
+        &self,
+        core_id: u32,
+        callback: js_sys::Function,
+    ) -> Result<Subscription, SaturnError> {
+        let api = self.api.clone();
+        let label = self.label.clone();
+        let timeout_ms = self.timeout_ms;
+
+        Ok(subscription::spawn(async move {
+            let result = async {
+                let prefix = map_prefix("INV4", "Multisig", &core_id);
+
+                let mut blocks = api.blocks().subscribe_best().await?;
+                let mut known: HashMap<H256, Vec<u8>> = HashMap::new();
+
+                while let Some(block) = blocks.next().await {
+                    let storage = TimedStorage::new(api.storage().at(block?.hash()), timeout_ms);
+
+                    let mut changes = ProposalChanges::default();
+                    let mut current = HashMap::new();
+
+                    for key in keys_with_prefix(&storage, &prefix).await? {
+                        let call_hash = multisig_key_call_hash(&key)?;
+
+                        let Some(proposal) = storage
+                            .fetch(&tinkernet::storage().inv4().multisig(core_id, call_hash))
+                            .await?
+                        else {
+                            continue;
+                        };
+
+                        let encoded = proposal.encode();
+                        match known.remove(&call_hash) {
+                            None => changes
+                                .added
+                                .push(proposal_with_call(&api, call_hash, proposal)),
+                            Some(previous) if previous != encoded => changes
+                                .updated
+                                .push(proposal_with_call(&api, call_hash, proposal)),
+                            Some(_) => {}
+                        }
+                        current.insert(call_hash, encoded);
+                    }
+
+                    changes.removed = known.into_keys().map(js_signer::to_hex).collect();
+                    known = current;
+
+                    if !changes.is_empty() {
+                        let changes = serde_wasm_bindgen::to_value(&changes)
+                            .map_err(|e| SaturnError::encoding(e.to_string()))?;
+                        let _ = callback.call1(&JsValue::null(), &changes);
+                    }
+                }
+
+                Ok::<(), SaturnError>(())
+            }
+            .await;
+
+            if let Err(e) = result {
+                utils::console_log!(label = label.as_deref(); "rust subscribe_proposal_changes error: {:?}", e);
+            }
+        }))
+    }
+
+    /// Everything an app usually needs on load, in one call: `{ genesis_hash, finalized_number,
+    /// finalized_hash, spec_version, chain_name }`.
+    ///
+    /// The genesis hash and spec version come from the client's cached runtime information, only
+    /// the finalized head and chain name hit the node.
+    #[wasm_bindgen]
+    pub async fn chain_state(&self) -> Result<JsValue, SaturnError> {
+        let finalized_hash = self.api.rpc().finalized_head().await?;
+
+        let finalized_header =
+            self.api
+                .rpc()
+                .header(Some(finalized_hash))
+                .await?
+                .ok_or(SaturnError::network(String::from(
+                    "Finalized header not found",
+                )))?;
+
+        let chain_name = self.api.rpc().system_chain().await?;
+
+        let state = ChainState {
+            genesis_hash: js_signer::to_hex(self.api.genesis_hash()),
+            finalized_number: finalized_header.number,
+            finalized_hash: js_signer::to_hex(finalized_hash),
+            spec_version: self.api.runtime_version().spec_version,
+            chain_name,
+        };
+
+        serde_wasm_bindgen::to_value(&state).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// The account's free voting balance as a percentage of the core token's total issuance.
+    ///
+    /// The ratio is computed in Perbill parts and only converted to `f64` at the end.
+    #[wasm_bindgen]
+    pub async fn voting_power_percent(
+        &self,
+        core_id: u32,
+        account: String,
+    ) -> Result<f64, SaturnError> {
+        let account_id = AccountId32::from_str(&account)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let storage = self.storage_at(None).await?;
+
+        let total_issuance = storage
+            .fetch_or_default(&tinkernet::storage().core_assets().total_issuance(core_id))
+            .await?;
+
+        if total_issuance == 0 {
+            return Ok(0.0);
+        }
+
+        let balance = storage
+            .fetch_or_default(
+                &tinkernet::storage()
+                    .core_assets()
+                    .accounts(&account_id, core_id),
+            )
+            .await?;
+
+        let parts = Perbill::from_rational(balance.free, total_issuance).deconstruct();
+
+        // One percent is 10_000_000 Perbill parts.
+        Ok(parts as f64 / 10_000_000.0)
+    }
+
+    /// The fee assets the connected runtime accepts, read from the `FeeAsset` type in its
+    /// metadata, so assets added by a runtime upgrade show up without an SDK release.
+    #[wasm_bindgen]
+    pub fn supported_fee_assets(&self) -> Result<JsValue, SaturnError> {
+        let assets: Vec<FeeAssetInfo> = self
+            .fee_asset_variants()?
+            .into_iter()
+            .map(|(index, symbol)| FeeAssetInfo { index, symbol })
+            .collect();
+
+        serde_wasm_bindgen::to_value(&assets).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// The fee asset named by one of the connected runtime's `FeeAsset` variants, e.g. "KSM",
+    /// ignoring case. Fails with an `InvalidInput` error for names the runtime doesn't have.
+    #[wasm_bindgen]
+    pub fn fee_asset(&self, name: String) -> Result<JsFeeAsset, SaturnError> {
+        let variants = self.fee_asset_variants()?;
+
+        variants
+            .iter()
+            .find(|(_, variant)| variant.eq_ignore_ascii_case(&name))
+            .map(|(_, variant)| JsFeeAsset {
+                name: variant.clone(),
+            })
+            .ok_or(SaturnError::invalid_input(format!(
+                "Unknown fee asset {}, expected one of {}",
+                name,
+                variants
+                    .iter()
+                    .map(|(_, variant)| variant.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )))
+    }
+
+    /// Diagnoses configuration problems that keep a core from ever passing a vote, returning
+    /// `{ member_count, total_issuance, can_pass_votes, warnings }`.
+    ///
+    /// `can_pass_votes` assumes every member votes aye with their whole free balance.
+    #[wasm_bindgen]
+    pub async fn core_health(&self, core_id: u32) -> Result<JsValue, SaturnError> {
+        let storage = self.storage_at(None).await?;
+
+        let core = storage
+            .fetch(&tinkernet::storage().inv4().core_storage(core_id))
+            .await?
+            .ok_or(SaturnError::invalid_input(format!(
+                "Core {} not found",
+                core_id
+            )))?;
+
+        let total_issuance = storage
+            .fetch_or_default(&tinkernet::storage().core_assets().total_issuance(core_id))
+            .await?;
+
+        let members = self.core_member_accounts(&storage, core_id).await?;
+
+        let mut member_weight: u128 = 0;
+        for member in &members {
+            let balance = storage
+                .fetch_or_default(&tinkernet::storage().core_assets().accounts(member, core_id))
+                .await?;
+
+            member_weight = member_weight.saturating_add(balance.free);
+        }
+
+        let minimum_support = core.minimum_support.0;
+        let required_approval = core.required_approval.0;
+
+        let mut warnings = Vec::new();
+        if members.is_empty() {
+            warnings.push(String::from("The core has no members"));
+        }
+        if total_issuance == 0 {
+            warnings.push(String::from("The core token has zero total issuance"));
+        }
+        if Perbill::from_rational(member_weight, total_issuance.max(1)) < minimum_support {
+            warnings.push(format!(
+                "Members hold less than the {:?} minimum support",
+                minimum_support
+            ));
+        }
+        if minimum_support.is_zero() {
+            warnings.push(String::from(
+                "Minimum support is 0%, a single aye vote is enough support",
+            ));
+        }
+        if required_approval.is_zero() {
+            warnings.push(String::from(
+                "Required approval is 0%, nay votes can't block proposals",
+            ));
+        }
+
+        let health = CoreHealth {
+            member_count: members.len() as u32,
+            total_issuance: total_issuance.to_string(),
+            can_pass_votes: tally_passes(
+                member_weight,
+                0,
+                total_issuance,
+                minimum_support,
+                required_approval,
+            ),
+            warnings,
+        };
+
+        serde_wasm_bindgen::to_value(&health).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// Every vote recorded on a proposal as `{ account, aye, weight }`, plus the tally totals.
+    ///
+    /// Votes are paginated in account order with `offset` (default 0) and `limit` (default and
+    /// maximum `MAX_VOTES_PAGE`).
+    #[wasm_bindgen]
+    pub async fn proposal_votes(
+        &self,
+        core_id: u32,
+        call_hash: String,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<JsValue, SaturnError> {
+        use tinkernet::runtime_types::pallet_inv4::voting::Vote;
+
+        let call_hash = parse_hash(&call_hash)?;
+
+        let proposal = self
+            .storage_at(None)
+            .await?
+            .fetch(&tinkernet::storage().inv4().multisig(core_id, call_hash))
+            .await?
+            .ok_or(SaturnError::invalid_input(format!(
+                "Proposal {} not found in core {}",
+                js_signer::to_hex(call_hash),
+                core_id
+            )))?;
+
+        let records = proposal.tally.records.0;
+        let limit = limit.unwrap_or(MAX_VOTES_PAGE).min(MAX_VOTES_PAGE);
+
+        let votes = records
+            .iter()
+            .skip(offset.unwrap_or(0) as usize)
+            .take(limit as usize)
+            .map(|(account, vote)| {
+                let (aye, weight) = match vote {
+                    Vote::Aye(weight) => (true, weight),
+                    Vote::Nay(weight) => (false, weight),
+                };
+
+                ProposalVote {
+                    account: self.address(account),
+                    aye,
+                    weight: weight.to_string(),
+                }
+            })
+            .collect();
+
+        let result = ProposalVotes {
+            votes,
+            ayes: proposal.tally.ayes.to_string(),
+            nays: proposal.tally.nays.to_string(),
+            voter_count: records.len() as u32,
+        };
+
+        serde_wasm_bindgen::to_value(&result).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// How `account` voted on a proposal, as `{ voted, aye, weight }`. Accounts that haven't
+    /// voted return `voted` false, a null `aye` and a weight of "0".
+    #[wasm_bindgen]
+    pub async fn get_account_vote(
+        &self,
+        core_id: u32,
+        call_hash: String,
+        account: String,
+    ) -> Result<JsValue, SaturnError> {
+        use tinkernet::runtime_types::pallet_inv4::voting::Vote;
+
+        let call_hash = parse_hash(&call_hash)?;
+        let account_id = AccountId32::from_str(&account)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let proposal = self
+            .storage_at(None)
+            .await?
+            .fetch(&tinkernet::storage().inv4().multisig(core_id, call_hash))
+            .await?
+            .ok_or(SaturnError::invalid_input(format!(
+                "Proposal {} not found in core {}",
+                js_signer::to_hex(call_hash),
+                core_id
+            )))?;
+
+        let vote = proposal
+            .tally
+            .records
+            .0
+            .iter()
+            .find(|(voter, _)| *voter == account_id)
+            .map(|(_, vote)| match vote {
+                Vote::Aye(weight) => (true, *weight),
+                Vote::Nay(weight) => (false, *weight),
+            });
+
+        let account_vote = AccountVote {
+            voted: vote.is_some(),
+            aye: vote.map(|(aye, _)| aye),
+            weight: vote.map_or(0, |(_, weight)| weight).to_string(),
+        };
+
+        serde_wasm_bindgen::to_value(&account_vote)
+            .map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// Whether the account may open proposals in the core.
+    ///
+    /// INV4 has no proposal creation threshold, `operate_multisig` only requires the caller to
+    /// hold some of the core's voting token, so any non-zero free balance qualifies.
+    #[wasm_bindgen]
+    pub async fn has_proposal_power(
+        &self,
+        core_id: u32,
+        account: String,
+    ) -> Result<bool, SaturnError> {
+        let account_id = AccountId32::from_str(&account)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let storage = self.storage_at(None).await?;
+
+        storage
+            .fetch(&tinkernet::storage().inv4().core_storage(core_id))
+            .await?
+            .ok_or(SaturnError::invalid_input(format!(
+                "Core {} not found",
+                core_id
+            )))?;
+
+        let balance = storage
+            .fetch_or_default(
+                &tinkernet::storage()
+                    .core_assets()
+                    .accounts(&account_id, core_id),
+            )
+            .await?;
+
+        Ok(balance.free > 0)
+    }
+
+    /// Pages through a core's pending proposals, returning each one's decoded inner call, tally
+    /// and metadata in a single pass, plus the `next_key` cursor to resume from.
+    ///
+    /// `call` is null when the stored call can't be decoded with the current metadata, in which
+    /// case `call_data` still carries the raw bytes.
+    #[wasm_bindgen]
+    pub async fn proposals_with_calls(
+        &self,
+        core_id: u32,
+        start_key: Option<String>,
+        page_size: u32,
+    ) -> Result<JsValue, SaturnError> {
+        let start_key = start_key
+            .map(|key| hex::decode(key.trim_start_matches("0x")))
+            .transpose()
+            .map_err(|e| SaturnError::invalid_input(format!("Invalid start_key: {}", e)))?;
+
+        let page_size = page_size.clamp(1, STORAGE_PAGE_SIZE);
+        let prefix = map_prefix("INV4", "Multisig", &core_id);
+
+        let storage = self.storage_at(None).await?;
+
+        let keys = storage
+            .fetch_keys(&prefix, page_size, start_key.as_deref())
+            .await?;
+
+        let mut proposals = Vec::new();
+        for key in &keys {
+            let call_hash = multisig_key_call_hash(&key.0)?;
+
+            let Some(proposal) = storage
+                .fetch(&tinkernet::storage().inv4().multisig(core_id, call_hash))
+                .await?
+            else {
+                continue;
+            };
+
+            proposals.push(proposal_with_call(&self.api, call_hash, proposal));
+        }
+
+        let next_key = (keys.len() == page_size as usize)
+            .then(|| keys.last().map(|key| js_signer::to_hex(&key.0)))
+            .flatten();
+
+        let page = ProposalsPage {
+            proposals,
+            next_key,
+        };
+
+        serde_wasm_bindgen::to_value(&page).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// Looks up the handle a core has set in its metadata.
+    ///
+    /// INV4 has no handle registry, so handles follow the same convention as the rest of a core's
+    /// metadata: it's a JSON object, and the handle is its `handle` string field. Cores whose
+    /// metadata isn't JSON, or has no handle, resolve to null.
+    #[wasm_bindgen]
+    pub async fn core_handle(&self, core_id: u32) -> Result<Option<String>, SaturnError> {
+        let core = self
+            .storage_at(None)
+            .await?
+            .fetch(&tinkernet::storage().inv4().core_storage(core_id))
+            .await?
+            .ok_or(SaturnError::invalid_input(format!(
+                "Core {} not found",
+                core_id
+            )))?;
+
+        Ok(metadata_handle(&core.metadata.0))
+    }
+
+    /// Resolves a core handle, as returned by `core_handle`, back to its core id. Handles are
+    /// compared case-insensitively.
+    ///
+    /// Since handles aren't indexed on chain this reads the metadata of every core, so apps
+    /// resolving handles often should cache the result.
+    #[wasm_bindgen]
+    pub async fn core_id_from_handle(&self, handle: String) -> Result<u32, SaturnError> {
+        let storage = self.storage_at(None).await?;
+
+        let prefix = storage_prefix("INV4", "CoreStorage");
+
+        for key in keys_with_prefix(&storage, &prefix).await? {
+            let core_id = key_suffix_u32(&key, "CoreStorage")?;
+
+            let Some(core) = storage
+                .fetch(&tinkernet::storage().inv4().core_storage(core_id))
+                .await?
+            else {
+                continue;
+            };
+
+            if metadata_handle(&core.metadata.0)
+                .is_some_and(|core_handle| core_handle.eq_ignore_ascii_case(&handle))
+            {
+                return Ok(core_id);
+            }
+        }
+
+        Err(SaturnError::invalid_input(format!(
+            "No core with handle {}",
+            handle
+        )))
+    }
+
+    /// Weighs a pending proposal's inner call through the `TransactionPaymentCallApi` runtime
+    /// API and returns `{ ref_time, proof_size, max_ref_time, max_proof_size, fits_in_block }`.
+    ///
+    /// Dispatch weights don't depend on the origin, so this is the weight the call adds when the
+    /// core executes it. The vote that triggers the execution adds its own weight on top. The
+    /// limits are those of a single normal class extrinsic.
+    #[wasm_bindgen]
+    pub async fn proposal_execution_weight(
+        &self,
+        core_id: u32,
+        call_hash: String,
+    ) -> Result<JsValue, SaturnError> {
+        let call_hash = parse_hash(&call_hash)?;
+
+        let proposal = self
+            .storage_at(None)
+            .await?
+            .fetch(&tinkernet::storage().inv4().multisig(core_id, call_hash))
+            .await?
+            .ok_or(SaturnError::invalid_input(format!(
+                "Proposal {} not found in core {}",
+                js_signer::to_hex(call_hash),
+                core_id
+            )))?;
+
+        let call_data = proposal.actual_call.0;
+        let call_len = call_data.len() as u32;
+
+        let mut parameters = call_data;
+        call_len.encode_to(&mut parameters);
+
+        let info = self
+            .api
+            .rpc()
+            .state_call(
+                "TransactionPaymentCallApi_query_call_info",
+                Some(&parameters),
+                None,
+            )
+            .await?;
+
+        // RuntimeDispatchInfo starts with the call's `Weight`, whose fields are both compact.
+        let (Compact(ref_time), Compact(proof_size)) =
+            <(Compact<u64>, Compact<u64>)>::decode(&mut &info[..])
+                .map_err(|e| SaturnError::encoding(format!("Failed to decode call info: {}", e)))?;
+
+        let block_weights = self
+            .api
+            .constants()
+            .at(&tinkernet::constants().system().block_weights())?;
+
+        let limit = block_weights
+            .per_class
+            .normal
+            .max_extrinsic
+            .unwrap_or(block_weights.max_block);
+
+        let weight = ExecutionWeight {
+            ref_time,
+            proof_size,
+            max_ref_time: limit.ref_time,
+            max_proof_size: limit.proof_size,
+            fits_in_block: ref_time <= limit.ref_time && proof_size <= limit.proof_size,
+        };
+
+        serde_wasm_bindgen::to_value(&weight).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// For each of the core's pending proposals in `call_hashes`, whether it can still pass and
+    /// how much more aye weight it needs: `[{ call_hash, can_pass, ayes_needed }]`.
+    ///
+    /// A proposal can pass when the issuance that hasn't voted yet is enough to get it over the
+    /// core's thresholds. `ayes_needed` is null for those that can't, and "0" for those already
+    /// passing.
+    #[wasm_bindgen]
+    pub async fn proposals_outlook(
+        &self,
+        core_id: u32,
+        call_hashes: Vec<String>,
+    ) -> Result<JsValue, SaturnError> {
+        let call_hashes = call_hashes
+            .iter()
+            .map(|call_hash| parse_hash(call_hash))
+            .collect::<Result<Vec<H256>, SaturnError>>()?;
+
+        let storage = self.storage_at(None).await?;
+
+        let core = storage
+            .fetch(&tinkernet::storage().inv4().core_storage(core_id))
+            .await?
+            .ok_or(SaturnError::invalid_input(format!(
+                "Core {} not found",
+                core_id
+            )))?;
+
+        let total_issuance = storage
+            .fetch_or_default(&tinkernet::storage().core_assets().total_issuance(core_id))
+            .await?;
+
+        let minimum_support = core.minimum_support.0;
+        let required_approval = core.required_approval.0;
+
+        let mut outlook = Vec::new();
+        for call_hash in call_hashes {
+            let proposal = storage
+                .fetch(&tinkernet::storage().inv4().multisig(core_id, call_hash))
+                .await?
+                .ok_or(SaturnError::invalid_input(format!(
+                    "Proposal {} not found in core {}",
+                    js_signer::to_hex(call_hash),
+                    core_id
+                )))?;
+
+            let ayes = proposal.tally.ayes;
+            let nays = proposal.tally.nays;
+            let passes_with = |extra_ayes: u128| {
+                tally_passes(
+                    ayes.saturating_add(extra_ayes),
+                    nays,
+                    total_issuance,
+                    minimum_support,
+                    required_approval,
+                )
+            };
+
+            let not_voted = total_issuance.saturating_sub(ayes.saturating_add(nays));
+
+            // Extra ayes only ever help, so the smallest sufficient amount can be bisected.
+            let ayes_needed = passes_with(not_voted).then(|| {
+                let (mut low, mut high) = (0, not_voted);
+                while low < high {
+                    let mid = low + (high - low) / 2;
+                    if passes_with(mid) {
+                        high = mid;
+                    } else {
+                        low = mid + 1;
+                    }
+                }
+                low
+            });
+
+            outlook.push(ProposalOutlook {
+                call_hash: js_signer::to_hex(call_hash),
+                can_pass: ayes_needed.is_some(),
+                ayes_needed: ayes_needed.map(|ayes_needed| ayes_needed.to_string()),
+            });
+        }
+
+        serde_wasm_bindgen::to_value(&outlook).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// The node's current metadata, SCALE encoded as in `tinkernet.scale`, for apps that cache
+    /// it to decode calls and events while offline.
+    #[wasm_bindgen]
+    pub async fn export_metadata(&self) -> Result<js_sys::Uint8Array, SaturnError> {
+        let response = self
+            .api
+            .rpc()
+            .state_call("Metadata_metadata", None, None)
+            .await?;
+
+        // The runtime API returns the metadata as `OpaqueMetadata`, i.e. length prefixed bytes.
+        let metadata = Vec::<u8>::decode(&mut &response[..])
+            .map_err(|e| SaturnError::encoding(format!("Failed to decode metadata: {}", e)))?;
+
+        Ok(js_sys::Uint8Array::from(metadata.as_slice()))
+    }
+
+    /// Lists all of the core's pending proposals, shaped like `proposals_with_calls` entries.
+    /// Cores with no open proposals return an empty array.
+    #[wasm_bindgen]
+    pub async fn get_multisig_proposals(&self, core_id: u32) -> Result<JsValue, SaturnError> {
+        let storage = self.storage_at(None).await?;
+
+        let prefix = map_prefix("INV4", "Multisig", &core_id);
+
+        let mut proposals = Vec::new();
+        for key in keys_with_prefix(&storage, &prefix).await? {
+            let call_hash = multisig_key_call_hash(&key)?;
+
+            let Some(proposal) = storage
+                .fetch(&tinkernet::storage().inv4().multisig(core_id, call_hash))
+                .await?
+            else {
+                continue;
+            };
+
+            proposals.push(proposal_with_call(&self.api, call_hash, proposal));
+        }
+
+        serde_wasm_bindgen::to_value(&proposals).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// A single pending proposal with its current tally, shaped like `proposals_with_calls`
+    /// entries. Returns null when the core has no proposal with that call hash.
+    #[wasm_bindgen]
+    pub async fn get_proposal(
+        &self,
+        core_id: u32,
+        call_hash: String,
+    ) -> Result<JsValue, SaturnError> {
+        let call_hash = parse_hash(&call_hash)?;
+
+        let Some(proposal) = self
+            .storage_at(None)
+            .await?
+            .fetch(&tinkernet::storage().inv4().multisig(core_id, call_hash))
+            .await?
+        else {
+            return Ok(JsValue::null());
+        };
+
+        let proposal = proposal_with_call(&self.api, call_hash, proposal);
+
+        serde_wasm_bindgen::to_value(&proposal).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// The core's settings as `{ account, metadata, minimum_support, required_approval,
+    /// frozen_tokens }`, with the thresholds as fractions between 0 and 1. Returns null for
+    /// cores that don't exist.
+    ///
+    /// `at_block` optionally pins the read to a historical block hash, as in `get_voting_balance`.
+    #[wasm_bindgen]
+    pub async fn get_core_info(
+        &self,
+        core_id: u32,
+        at_block: Option<String>,
+    ) -> Result<JsValue, SaturnError> {
+        let Some(core) = self
+            .storage_at(at_block)
+            .await?
+            .fetch(&tinkernet::storage().inv4().core_storage(core_id))
+            .await?
+        else {
+            return Ok(JsValue::null());
+        };
+
+        let info = CoreInfo {
+            account: self.address(&core.account),
+            metadata: display_bytes(&core.metadata.0),
+            minimum_support: perbill_fraction(core.minimum_support.0),
+            required_approval: perbill_fraction(core.required_approval.0),
+            frozen_tokens: core.frozen_tokens,
+        };
+
+        serde_wasm_bindgen::to_value(&info).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// Pages through every core as `{ cores: [{ core_id, metadata }], next_key }`, in storage
+    /// key order. Pass `next_key` as `start_key` to get the following page; it's null on the
+    /// last one. `limit` defaults to and is capped at `STORAGE_PAGE_SIZE`.
+    #[wasm_bindgen]
+    pub async fn list_cores(
+        &self,
+        start_key: Option<String>,
+        limit: Option<u32>,
+    ) -> Result<JsValue, SaturnError> {
+        let start_key = start_key
+            .map(|key| hex::decode(key.trim_start_matches("0x")))
+            .transpose()
+            .map_err(|e| SaturnError::invalid_input(format!("Invalid start_key: {}", e)))?;
+
+        let limit = limit
+            .unwrap_or(STORAGE_PAGE_SIZE)
+            .clamp(1, STORAGE_PAGE_SIZE);
+
+        let prefix = storage_prefix("INV4", "CoreStorage");
+
+        let storage = self.storage_at(None).await?;
+
+        let keys = storage
+            .fetch_keys(&prefix, limit, start_key.as_deref())
+            .await?;
+
+        let mut cores = Vec::new();
+        for key in &keys {
+            let core_id = key_suffix_u32(&key.0, "CoreStorage")?;
+
+            let Some(core) = storage
+                .fetch(&tinkernet::storage().inv4().core_storage(core_id))
+                .await?
+            else {
+                continue;
+            };
+
+            cores.push(CoreSummary {
+                core_id,
+                metadata: display_bytes(&core.metadata.0),
+            });
+        }
+
+        let next_key = (keys.len() == limit as usize)
+            .then(|| keys.last().map(|key| js_signer::to_hex(&key.0)))
+            .flatten();
+
+        let page = CoresPage { cores, next_key };
+
+        serde_wasm_bindgen::to_value(&page).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// Every core the account holds voting tokens in, as `[{ core_id, free, reserved }]`.
+    /// Accounts in no cores return an empty array.
+    #[wasm_bindgen]
+    pub async fn get_account_cores(&self, account: String) -> Result<JsValue, SaturnError> {
+        let account_id = AccountId32::from_str(&account)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let storage = self.storage_at(None).await?;
+
+        let prefix = map_prefix("CoreAssets", "Accounts", &account_id);
+
+        let mut cores = Vec::new();
+        for key in keys_with_prefix(&storage, &prefix).await? {
+            // The core id is the second key, Twox64Concat hashed, so its raw bytes end the key.
+            let core_id = key_suffix_u32(&key, "Accounts")?;
+
+            let balance = storage
+                .fetch_or_default(
+                    &tinkernet::storage()
+                        .core_assets()
+                        .accounts(&account_id, core_id),
+                )
+                .await?;
+
+            cores.push(AccountCore {
+                core_id,
+                free: balance.free.to_string(),
+                reserved: balance.reserved.to_string(),
+            });
+        }
+
+        serde_wasm_bindgen::to_value(&cores).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// Refetches the runtime version and metadata after a runtime upgrade, returning the new
+    /// spec version.
+    ///
+    /// The client caches both when it connects, so signing doesn't refetch them, but it doesn't
+    /// follow upgrades on its own. `Call`s built earlier share the cache and pick up the change.
+    #[wasm_bindgen]
+    pub async fn refresh_runtime_version(&self) -> Result<u32, SaturnError> {
+        let runtime_version = self.api.rpc().runtime_version(None).await?;
+
+        let metadata = self.api.rpc().metadata().await?;
+
+        let spec_version = runtime_version.spec_version;
+        self.api.set_runtime_version(runtime_version);
+        self.api.set_metadata(metadata);
+
+        Ok(spec_version)
+    }
+
+    /// The core's members with a voting balance, as `[{ account, weight, share }]` sorted by
+    /// weight, heaviest first. `share` is the fraction of the token's total issuance held.
+    ///
+    /// Members are read from `INV4.CoreMembers`, since `CoreAssets.Accounts` is keyed by
+    /// account first and can't be iterated per core.
+    ///
+    /// `at_block` optionally pins the reads to a historical block hash, as in `get_voting_balance`.
+    #[wasm_bindgen]
+    pub async fn get_core_members(
+        &self,
+        core_id: u32,
+        at_block: Option<String>,
+    ) -> Result<JsValue, SaturnError> {
+        let storage = self.storage_at(at_block).await?;
+
+        let total_issuance = storage
+            .fetch_or_default(&tinkernet::storage().core_assets().total_issuance(core_id))
+            .await?;
+
+        let mut members = Vec::new();
+        for member in self.core_member_accounts(&storage, core_id).await? {
+            let balance = storage
+                .fetch_or_default(
+                    &tinkernet::storage()
+                        .core_assets()
+                        .accounts(&member, core_id),
+                )
+                .await?;
+
+            if balance.free > 0 {
+                members.push((member, balance.free));
+            }
+        }
+
+        members.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        let members = members
+            .into_iter()
+            .map(|(account, weight)| CoreMember {
+                account: self.address(&account),
+                weight: weight.to_string(),
+                share: weight as f64 / total_issuance.max(1) as f64,
+            })
+            .collect::<Vec<CoreMember>>();
+
+        serde_wasm_bindgen::to_value(&members).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// The nonce the account's next transaction needs, counting its transactions still in the
+    /// transaction pool. Use it to seed a local counter when submitting several calls in a row.
+    #[wasm_bindgen]
+    pub async fn account_next_nonce(&self, account: String) -> Result<u64, SaturnError> {
+        let account_id = AccountId32::from_str(&account)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let nonce = self
+            .api
+            .rpc()
+            .system_account_next_index(&account_id)
+            .await?;
+
+        Ok(nonce as u64)
+    }
+
+    /// Total issuance of the core's voting token as a decimal string, "0" for cores that don't
+    /// exist.
+    ///
+    /// `at_block` optionally pins the read to a historical block hash, as in `get_voting_balance`.
+    #[wasm_bindgen]
+    pub async fn get_total_issuance(
+        &self,
+        core_id: u32,
+        at_block: Option<String>,
+    ) -> Result<String, SaturnError> {
+        let total_issuance = self
+            .storage_at(at_block)
+            .await?
+            .fetch_or_default(&tinkernet::storage().core_assets().total_issuance(core_id))
+            .await?;
+
+        Ok(total_issuance.to_string())
+    }
+
+    /// Whether a proposal from `account` would execute right away, i.e. its voting balance
+    /// alone meets the core's minimum support and required approval. Accounts without voting
+    /// tokens return false.
+    #[wasm_bindgen]
+    pub async fn would_execute_immediately(
+        &self,
+        core_id: u32,
+        account: String,
+    ) -> Result<bool, SaturnError> {
+        let account_id = AccountId32::from_str(&account)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let storage = self.storage_at(None).await?;
+
+        let core = storage
+            .fetch(&tinkernet::storage().inv4().core_storage(core_id))
+            .await?
+            .ok_or(SaturnError::invalid_input(format!(
+                "Core {} not found",
+                core_id
+            )))?;
+
+        let balance = storage
+            .fetch_or_default(
+                &tinkernet::storage()
+                    .core_assets()
+                    .accounts(&account_id, core_id),
+            )
+            .await?;
+
+        let total_issuance = storage
+            .fetch_or_default(&tinkernet::storage().core_assets().total_issuance(core_id))
+            .await?;
+
+        // The proposer's vote is the only one cast when the proposal is created.
+        Ok(tally_passes(
+            balance.free,
+            0,
+            total_issuance,
+            core.minimum_support.0,
+            core.required_approval.0,
+        ))
+    }
+}
+
+impl Saturn {
+    /// SS58 address of `account` with the chain's prefix, see `ss58_address`.
+    fn address(&self, account: &AccountId32) -> String {
+        ss58_address(&self.api, account)
+    }
+
+    /// Index and name of every variant of the runtime's `FeeAsset`, the type of
+    /// `create_core`'s `creation_fee_asset`.
+    fn fee_asset_variants(&self) -> Result<Vec<(u8, String)>, SaturnError> {
+        let metadata = self.api.metadata();
+
+        let type_id = metadata
+            .pallet_by_name("INV4")
+            .and_then(|pallet| pallet.call_variant_by_name("create_core"))
+            .and_then(|variant| {
+                variant
+                    .fields
+                    .iter()
+                    .find(|field| field.name.as_deref() == Some("creation_fee_asset"))
+            })
+            .map(|field| field.ty.id)
+            .ok_or(SaturnError::encoding(String::from(
+                "FeeAsset type not found in metadata",
+            )))?;
+
+        let Some(scale_info::TypeDef::Variant(fee_asset)) = metadata
+            .types()
+            .resolve(type_id)
+            .map(|ty| &ty.type_def)
+        else {
+            return Err(SaturnError::encoding(String::from("FeeAsset is not an enum")));
+        };
+
+        Ok(fee_asset
+            .variants
+            .iter()
+            .map(|variant| (variant.index, variant.name.clone()))
+            .collect())
+    }
+
+    fn ss58_prefix(&self) -> Result<u16, SaturnError> {
+        self.api
+            .constants()
+            .at(&tinkernet::constants().system().ss58_prefix())
+            .map_err(SaturnError::from)
+    }
+
+    /// Rejects metadata longer than the runtime's `MaxMetadata`, which would only fail once
+    /// submitted.
+    fn ensure_metadata_fits(&self, metadata: &str) -> Result<(), SaturnError> {
+        let max_metadata = self.max_metadata_length()?;
+
+        if metadata.len() > max_metadata as usize {
+            return Err(SaturnError::invalid_input(format!(
+                "Metadata is {} bytes, longer than the maximum of {}",
+                metadata.len(),
+                max_metadata
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn new_call(&self, call: Payload<Composite<()>>) -> Call {
+        Call {
+            api: self.api.clone(),
+            call,
+            online: self.online,
+            label: self.label.clone(),
+            event_pallets: None,
+            find_core_created: true,
+            timeout_ms: self.timeout_ms,
+            default_mortality: self.default_mortality,
+            default_tip: self.default_tip.clone(),
+        }
+    }
+
+    /// Decodes SCALE encoded call data against the metadata into a dynamic `Call`, making sure
+    /// it re-encodes to exactly the same bytes.
+    fn call_from_call_data(&self, call_data: &[u8]) -> Result<Call, SaturnError> {
+        let decoded = call_decoding::decode_call(&self.api.metadata(), call_data)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let call = self.new_call(subxt::dynamic::tx(
+            decoded.pallet,
+            decoded.call,
+            decoded.fields,
+        ));
+
+        let reencoded = self.api.tx().call_data(&call.call)?;
+
+        if reencoded != call_data {
+            return Err(SaturnError::encoding(String::from(
+                "Call data doesn't round trip through the metadata",
+            )));
+        }
+
+        Ok(call)
+    }
+
+    fn ensure_pallet(&self, pallet: &str) -> Result<(), SaturnError> {
+        self.api
+            .metadata()
+            .pallet_by_name(pallet)
+            .map(|_| ())
+            .ok_or(SaturnError::invalid_input(format!(
+                "{} pallet not found in metadata",
+                pallet
+            )))
+    }
+
+    fn ocif_min_stake(&self) -> Result<u128, SaturnError> {
+        self.ensure_pallet("OcifStaking")?;
+
+        self.api
+            .constants()
+            .at(&tinkernet::constants()
+                .ocif_staking()
+                .minimum_staking_amount())
+            .map_err(SaturnError::from)
+    }
+
+    /// Storage client pinned to `at_block`, see `storage_at`.
+    async fn storage_at(&self, at_block: Option<String>) -> Result<TimedStorage, SaturnError> {
+        storage_at(&self.api, self.timeout_ms, at_block).await
+    }
+
+    /// Every account registered in `INV4.CoreMembers` for the core.
+    async fn core_member_accounts(
+        &self,
+        storage: &TimedStorage,
+        core_id: u32,
+    ) -> Result<Vec<AccountId32>, SaturnError> {
+        let prefix = map_prefix("INV4", "CoreMembers", &core_id);
+
+        keys_with_prefix(storage, &prefix)
+            .await?
+            .into_iter()
+            .map(|key| key_suffix_account(&key, "CoreMembers"))
+            .collect()
+    }
+
+    /// Unclaimed core-side OCIF rewards of every past era the core was staked in.
+    async fn pending_core_rewards(&self, core_id: u32) -> Result<Vec<(u32, u128)>, SaturnError> {
+        self.ensure_pallet("OcifStaking")?;
+
+        let storage = self.storage_at(None).await?;
+
+        let current_era = storage
+            .fetch_or_default(&tinkernet::storage().ocif_staking().current_era())
+            .await?;
+
+        let prefix = map_prefix("OcifStaking", "CoreEraStake", &core_id);
+
+        let mut rewards = Vec::new();
+        for key in keys_with_prefix(&storage, &prefix).await? {
+            let era = key_suffix_u32(&key, "CoreEraStake")?;
+
+            if era >= current_era {
+                continue;
+            }
+
+            let Some(stake) = storage
+                .fetch(&tinkernet::storage().ocif_staking().core_era_stake(core_id, era))
+                .await?
+            else {
+                continue;
+            };
+
+            if stake.reward_claimed || !stake.active {
+                continue;
+            }
+
+            let Some(era_info) = storage
+                .fetch(&tinkernet::storage().ocif_staking().general_era_info(era))
+                .await?
+            else {
+                continue;
+            };
+
+            // Same split the pallet uses: the core's share of the era's active stake.
+            let amount =
+                Perbill::from_rational(stake.total, era_info.active_stake) * era_info.rewards.core;
+
+            rewards.push((era, amount));
+        }
+
+        rewards.sort_by_key(|(era, _)| *era);
+
+        Ok(rewards)
+    }
+
+    async fn block_hash_at(&self, block_number: u32) -> Result<H256, SaturnError> {
+        self.api
+            .rpc()
+            .block_hash(Some(block_number.into()))
+            .await?
+            .ok_or(SaturnError::invalid_input(format!(
+                "Block {} not found",
+                block_number
+            )))
+    }
+
+    /// Binary searches the finalized chain for a block that ran `spec_version`, relying on spec
+    /// versions only ever increasing.
+    async fn find_block_with_spec_version(&self, spec_version: u32) -> Result<H256, SaturnError> {
+        let finalized_hash = self.api.rpc().finalized_head().await?;
+
+        let finalized_number = self
+            .api
+            .rpc()
+            .header(Some(finalized_hash))
+            .await?
+            .ok_or(SaturnError::network(String::from(
+                "Finalized header not found",
+            )))?
+            .number;
+
+        let (mut low, mut high) = (0, finalized_number);
+        while low <= high {
+            let middle = low + (high - low) / 2;
+            let block_hash = self.block_hash_at(middle).await?;
+
+            let version = self
+                .api
+                .rpc()
+                .runtime_version(Some(block_hash))
+                .await?
+                .spec_version;
+
+            match version.cmp(&spec_version) {
+                std::cmp::Ordering::Equal => return Ok(block_hash),
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater if middle == 0 => break,
+                std::cmp::Ordering::Greater => high = middle - 1,
+            }
+        }
+
+        Err(SaturnError::invalid_input(format!(
+            "No finalized block runs spec version {}",
+            spec_version
+        )))
+    }
+
+    /// Mirrors the runtime's core account derivation: the blake2_256 hash of the
+    /// SCALE-encoded `(INV4::GenesisHash, core_id)` pair.
+    fn derive_core_account(&self, core_id: u32) -> Result<AccountId32, SaturnError> {
+        core_account(&self.api, core_id)
+    }
+}
+
+/// The account of `core_id`, derived from INV4's `GenesisHash` like the runtime does.
+fn core_account(
+    api: &OnlineClient<PolkadotConfig>,
+    core_id: u32,
+) -> Result<AccountId32, SaturnError> {
+    let genesis_hash = api
+        .constants()
+        .at(&tinkernet::constants().inv4().genesis_hash())?;
+
+    Ok(AccountId32(sp_core_hashing::blake2_256(
+        &(genesis_hash, core_id).encode(),
+    )))
+}
+
+/// Free balance of `account` in `fee_asset`: native TNKR from `System.Account`, KSM from
+/// `Tokens.Accounts`.
+async fn fee_asset_balance(
+    api: &OnlineClient<PolkadotConfig>,
+    storage: &TimedStorage,
+    account: &AccountId32,
+    fee_asset: &JsFeeAsset,
+) -> Result<u128, SaturnError> {
+    if fee_asset.is_tnkr() {
+        return Ok(storage
+            .fetch_or_default(&tinkernet::storage().system().account(account))
+            .await?
+            .data
+            .free);
+    }
+
+    if fee_asset.is_ksm() {
+        let ksm_asset_id = api
+            .constants()
+            .at(&tinkernet::constants().inv4().ksm_asset_id())?;
+
+        return Ok(storage
+            .fetch_or_default(
+                &tinkernet::storage()
+                    .tokens()
+                    .accounts(account, ksm_asset_id),
+            )
+            .await?
+            .free);
+    }
+
+    Err(SaturnError::invalid_input(format!(
+        "No known balance for fee asset {}",
+        fee_asset.name
+    )))
+}
+
+/// Storage key prefix of every entry of a storage item.
+fn storage_prefix(pallet: &str, entry: &str) -> Vec<u8> {
+    let mut prefix = sp_core_hashing::twox_128(pallet.as_bytes()).to_vec();
+    prefix.extend(sp_core_hashing::twox_128(entry.as_bytes()));
+    prefix
+}
+
+/// Storage key prefix of a map's entries whose first key is `first_key`, for maps hashed with
+/// `Blake2_128Concat` as INV4 and CoreAssets use.
+fn map_prefix(pallet: &str, entry: &str, first_key: &impl Encode) -> Vec<u8> {
+    let encoded_key = first_key.encode();
+
+    let mut prefix = storage_prefix(pallet, entry);
+    prefix.extend(sp_core_hashing::blake2_128(&encoded_key));
+    prefix.extend(encoded_key);
+    prefix
+}
+
+/// Whether a tally meets the core's thresholds: support is measured against the total
+/// issuance and approval against the votes cast, as in the INV4 pallet.
+fn tally_passes(
+    ayes: u128,
+    nays: u128,
+    total_issuance: u128,
+    minimum_support: Perbill,
+    required_approval: Perbill,
+) -> bool {
+    if ayes == 0 || total_issuance == 0 {
+        return false;
+    }
+
+    Perbill::from_rational(ayes, total_issuance) >= minimum_support
+        && Perbill::from_rational(ayes, ayes.saturating_add(nays)) >= required_approval
+}
+
+/// Storage client pinned to `at_block`, or to the latest block when omitted, whose reads fail
+/// with a `Timeout` error after `timeout_ms`.
+///
+/// Blocks beyond the node's pruning window are only available on archive nodes.
+async fn storage_at(
+    api: &OnlineClient<PolkadotConfig>,
+    timeout_ms: Option<u32>,
+    at_block: Option<String>,
+) -> Result<TimedStorage, SaturnError> {
+    let Some(at_block) = at_block else {
+        let storage = timeout::with_timeout(timeout_ms, "Storage read", api.storage().at_latest())
+            .await??;
+
+        return Ok(TimedStorage::new(storage, timeout_ms));
+    };
+
+    let block_hash = parse_hash(&at_block)?;
+
+    timeout::with_timeout(
+        timeout_ms,
+        "Storage read",
+        api.rpc().header(Some(block_hash)),
+    )
+    .await??
+    .ok_or(SaturnError::invalid_input(format!(
+        "Block {} not found, historical reads require an archive node",
+        at_block
+    )))?;
+
+    Ok(TimedStorage::new(api.storage().at(block_hash), timeout_ms))
+}
+
+/// `Storage` reads bounded by the client's `timeout_ms`, failing with a `Timeout` error
+/// instead of hanging on an unresponsive node.
+struct TimedStorage {
+    storage: Storage<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    timeout_ms: Option<u32>,
+}
+
+impl TimedStorage {
+    fn new(
+        storage: Storage<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+        timeout_ms: Option<u32>,
+    ) -> Self {
+        TimedStorage {
+            storage,
+            timeout_ms,
+        }
+    }
+
+    async fn fetch<Address>(
+        &self,
+        address: &Address,
+    ) -> Result<Option<Address::Target>, SaturnError>
+    where
+        Address: StorageAddress<IsFetchable = Yes>,
+    {
+        timeout::with_timeout(self.timeout_ms, "Storage read", self.storage.fetch(address))
+            .await?
+            .map_err(SaturnError::from)
+    }
+
+    async fn fetch_or_default<Address>(
+        &self,
+        address: &Address,
+    ) -> Result<Address::Target, SaturnError>
+    where
+        Address: StorageAddress<IsFetchable = Yes, IsDefaultable = Yes>,
+    {
+        timeout::with_timeout(
+            self.timeout_ms,
+            "Storage read",
+            self.storage.fetch_or_default(address),
+        )
+        .await?
+        .map_err(SaturnError::from)
+    }
+
+    async fn fetch_keys(
+        &self,
+        key: &[u8],
+        count: u32,
+        start_key: Option<&[u8]>,
+    ) -> Result<Vec<StorageKey>, SaturnError> {
+        timeout::with_timeout(
+            self.timeout_ms,
+            "Storage read",
+            self.storage.fetch_keys(key, count, start_key),
+        )
+        .await?
+        .map_err(SaturnError::from)
+    }
+}
+
+/// Every storage key starting with `prefix`, fetched page by page.
+async fn keys_with_prefix(
+    storage: &TimedStorage,
+    prefix: &[u8],
+) -> Result<Vec<Vec<u8>>, SaturnError> {
+    let mut keys = Vec::new();
+    loop {
+        let page = storage
+            .fetch_keys(prefix, STORAGE_PAGE_SIZE, keys.last().map(Vec::as_slice))
+            .await?;
+
+        let page_len = page.len();
+        keys.extend(page.into_iter().map(|key| key.0));
+
+        if page_len < STORAGE_PAGE_SIZE as usize {
+            return Ok(keys);
+        }
+    }
+}
+
+/// Wraps a call into `INV4.operate_multisig`, proposing it to the members of `core_id`.
+///
+/// Calls larger than INV4's `MaxCallSize` are rejected, and so are nested `operate_multisig`
+/// calls unless `allow_nested`, see `ensure_not_multisig`.
+fn multisig_payload(
+    api: &OnlineClient<PolkadotConfig>,
+    core_id: u32,
+    metadata: Option<String>,
+    fee_asset: &JsFeeAsset,
+    call: &Call,
+    allow_nested: bool,
+) -> Result<Payload<Composite<()>>, SaturnError> {
+    if !allow_nested {
+        ensure_not_multisig(call)?;
+    }
+
+    let call_data = api.tx().call_data(&call.call)?;
+
+    let max_call_size = api
+        .constants()
+        .at(&tinkernet::constants().inv4().max_call_size())?;
+
+    if call_data.len() > max_call_size as usize {
+        return Err(SaturnError::invalid_input(format!(
+            "Call is {} bytes, larger than the maximum of {}",
+            call_data.len(),
+            max_call_size
+        )));
+    }
+
+    let metadata = option_value(metadata.map(Value::from_bytes));
+
+    Ok(subxt::dynamic::tx(
+        "INV4",
+        "operate_multisig",
+        vec![
+            ("core_id", Value::u128(core_id as u128)),
+            ("metadata", metadata),
+            ("fee_asset", fee_asset.to_value()),
+            ("call", call.call.clone().into_value()),
+        ],
+    ))
+}
+
+/// `TransactionPayment` fee estimate for submitting `call`, in TNKR.
+async fn fee_estimate(
+    api: &OnlineClient<PolkadotConfig>,
+    call: &Payload<Composite<()>>,
+) -> Result<u128, SaturnError> {
+    let partial_signed = api
+        .tx()
+        .create_partial_signed_with_nonce(call, 0, Default::default())?;
+
+    // Fees only depend on the call and the extrinsic's length, not on who signs it.
+    let extrinsic = partial_signed.sign_with_address_and_signature(
+        &AccountId32([0; 32]).into(),
+        &MultiSignature::Sr25519([0; 64]),
+    );
+
+    extrinsic
+        .partial_fee_estimate()
+        .await
+        .map_err(SaturnError::from)
+}
+
+/// The core id and proposed call of an `INV4.operate_multisig`, read back from its fields.
+fn multisig_proposal(call: &Payload<Composite<()>>) -> Option<(u32, Payload<Composite<()>>)> {
+    use subxt::ext::scale_value::ValueDef;
+
+    if call.pallet_name() != "INV4" || call.call_name() != "operate_multisig" {
+        return None;
+    }
+
+    let Composite::Named(fields) = call.call_data() else {
+        return None;
+    };
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+    };
+
+    let core_id = u32::try_from(field("core_id")?.as_u128()?).ok()?;
+
+    // The call is a pallet variant wrapping the call variant, see `Payload::into_value`.
+    let ValueDef::Variant(pallet) = &field("call")?.value else {
+        return None;
+    };
+    let Some(Value {
+        value: ValueDef::Variant(inner),
+        ..
+    }) = pallet.values.values().next()
+    else {
+        return None;
+    };
+
+    Some((
+        core_id,
+        subxt::dynamic::tx(
+            pallet.name.clone(),
+            inner.name.clone(),
+            inner.values.clone(),
+        ),
+    ))
+}
+
+/// Rejects proposing an `INV4.operate_multisig`, which the core's origin can't vote with.
+fn ensure_not_multisig(call: &Call) -> Result<(), SaturnError> {
+    if call.call.pallet_name() == "INV4" && call.call.call_name() == "operate_multisig" {
+        return Err(SaturnError::invalid_input(String::from(
+            "Can't propose an operate_multisig call, pass allow_nested to propose it anyway",
+        )));
+    }
+
+    Ok(())
+}
+
+/// The `handle` field of a core's JSON metadata, if it has one.
+fn metadata_handle(metadata: &[u8]) -> Option<String> {
+    let metadata: serde_json::Value = serde_json::from_slice(metadata).ok()?;
+
+    metadata
+        .get("handle")?
+        .as_str()
+        .map(|handle| handle.to_string())
+}
+
+/// Call hash of an `INV4.Multisig` entry, which its key ends with as the second map key is
+/// `Blake2_128Concat` hashed.
+fn multisig_key_call_hash(key: &[u8]) -> Result<H256, SaturnError> {
+    key.get(key.len().saturating_sub(32)..)
+        .and_then(|call_hash| call_hash.try_into().ok())
+        .map(H256)
+        .ok_or(SaturnError::encoding(String::from(
+            "Malformed Multisig key",
+        )))
+}
+
+/// The `u32` a storage key ends with, for keys whose last map key is a `u32` hashed with
+/// `Blake2_128Concat` or `Twox64Concat`, which both append the raw key. `what` names the
+/// storage entry for the error.
+fn key_suffix_u32(key: &[u8], what: &str) -> Result<u32, SaturnError> {
+    key.get(key.len().saturating_sub(4)..)
+        .and_then(|suffix| suffix.try_into().ok())
+        .map(u32::from_le_bytes)
+        .ok_or(SaturnError::encoding(format!("Malformed {} key", what)))
+}
+
+/// The account a storage key ends with, for keys whose last map key is an account hashed with
+/// `Blake2_128Concat`, like `key_suffix_u32`.
+fn key_suffix_account(key: &[u8], what: &str) -> Result<AccountId32, SaturnError> {
+    key.get(key.len().saturating_sub(32)..)
+        .and_then(|suffix| <[u8; 32]>::try_from(suffix).ok())
+        .map(AccountId32)
+        .ok_or(SaturnError::encoding(format!("Malformed {} key", what)))
+}
+
+/// SS58 address of `account` with the chain's prefix, falling back to the generic substrate
+/// format if the runtime doesn't declare one.
+fn ss58_address(api: &OnlineClient<PolkadotConfig>, account: &AccountId32) -> String {
+    match api
+        .constants()
+        .at(&tinkernet::constants().system().ss58_prefix())
+    {
+        Ok(prefix) => ss58::encode(account, prefix),
+        Err(_) => account.to_string(),
+    }
+}
+
+fn proposal_with_call(
+    api: &OnlineClient<PolkadotConfig>,
+    call_hash: H256,
+    proposal: MultisigProposal,
+) -> ProposalWithCall {
+    let call_data = proposal.actual_call.0;
+
+    ProposalWithCall {
+        call_hash: js_signer::to_hex(call_hash),
+        call: call_decoding::decode_call(&api.metadata(), &call_data)
+            .ok()
+            .map(|call| call.describe()),
+        call_data: js_signer::to_hex(&call_data),
+        proposer: ss58_address(api, &proposal.original_caller),
+        metadata: proposal.metadata.map(|metadata| display_bytes(&metadata.0)),
+        ayes: proposal.tally.ayes.to_string(),
+        nays: proposal.tally.nays.to_string(),
+        voter_count: proposal.tally.records.0.len() as u32,
+    }
+}
+
+/// The state changes `events`' INV4 events imply, see `SubmissionResult.predicted_state`.
+fn predicted_changes(
+    api: &OnlineClient<PolkadotConfig>,
+    events: &ExtrinsicEvents<PolkadotConfig>,
+) -> Result<Vec<PredictedChange>, SubxtError> {
+    use tinkernet::{inv4::events as inv4_events, runtime_types::pallet_inv4::voting::Vote};
+
+    let mut changes = Vec::new();
+
+    for event in events.iter() {
+        let event = event?;
+
+        let change = if let Some(minted) = event.as_event::<inv4_events::Minted>()? {
+            PredictedChange {
+                account: Some(ss58_address(api, &minted.target)),
+                amount: Some(minted.amount.to_string()),
+                ..PredictedChange::new("minted", minted.core_id)
+            }
+        } else if let Some(burned) = event.as_event::<inv4_events::Burned>()? {
+            PredictedChange {
+                account: Some(ss58_address(api, &burned.target)),
+                amount: Some(burned.amount.to_string()),
+                ..PredictedChange::new("burned", burned.core_id)
+            }
+        } else if let Some(started) = event.as_event::<inv4_events::MultisigVoteStarted>()? {
+            let (ayes, nays) = match started.votes_added {
+                Vote::Aye(votes) => (votes, 0),
+                Vote::Nay(votes) => (0, votes),
+            };
+
+            PredictedChange {
+                account: Some(ss58_address(api, &started.voter)),
+                call_hash: Some(js_signer::to_hex(started.call_hash)),
+                ayes: Some(ayes.to_string()),
+                nays: Some(nays.to_string()),
+                ..PredictedChange::new("proposal_opened", started.core_id)
+            }
+        } else if let Some(added) = event.as_event::<inv4_events::MultisigVoteAdded>()? {
+            PredictedChange {
+                account: Some(ss58_address(api, &added.voter)),
+                call_hash: Some(js_signer::to_hex(added.call_hash)),
+                ayes: Some(added.current_votes.ayes.to_string()),
+                nays: Some(added.current_votes.nays.to_string()),
+                ..PredictedChange::new("vote_added", added.core_id)
+            }
+        } else if let Some(withdrawn) = event.as_event::<inv4_events::MultisigVoteWithdrawn>()? {
+            PredictedChange {
+                account: Some(ss58_address(api, &withdrawn.voter)),
+                call_hash: Some(js_signer::to_hex(withdrawn.call_hash)),
+                ..PredictedChange::new("vote_withdrawn", withdrawn.core_id)
+            }
+        } else if let Some(executed) = event.as_event::<inv4_events::MultisigExecuted>()? {
+            PredictedChange {
+                call_hash: Some(js_signer::to_hex(executed.call_hash)),
+                ..PredictedChange::new("proposal_executed", executed.core_id)
+            }
+        } else if let Some(canceled) = event.as_event::<inv4_events::MultisigCanceled>()? {
+            PredictedChange {
+                call_hash: Some(js_signer::to_hex(canceled.call_hash)),
+                ..PredictedChange::new("proposal_canceled", canceled.core_id)
+            }
+        } else if let Some(set) = event.as_event::<inv4_events::ParametersSet>()? {
+            PredictedChange {
+                metadata: set.metadata.map(|metadata| display_bytes(&metadata)),
+                minimum_support: set
+                    .minimum_support
+                    .map(|perbill| perbill_fraction(perbill.0)),
+                required_approval: set
+                    .required_approval
+                    .map(|perbill| perbill_fraction(perbill.0)),
+                frozen_tokens: set.frozen_tokens,
+                ..PredictedChange::new("parameters_set", set.core_id)
+            }
+        } else {
+            continue;
+        };
+
+        changes.push(change);
+    }
+
+    Ok(changes)
+}
+
+/// Shows bytes as text when they're valid UTF-8, and as hex otherwise.
+fn display_bytes(bytes: &[u8]) -> String {
+    String::from_utf8(bytes.to_vec()).unwrap_or_else(|_| js_signer::to_hex(bytes))
+}
+
+/// Dynamic value of an `Option`.
+fn option_value(value: Option<Value>) -> Value {
+    match value {
+        Some(value) => Value::unnamed_variant("Some", [value]),
+        None => Value::unnamed_variant("None", []),
+    }
+}
+
+/// Checks that `parts` is a valid Perbill, i.e. at most one billion parts. `Perbill::from_parts`
+/// would silently clamp larger values to 100%.
+fn parse_perbill(parts: u32) -> Result<Perbill, SaturnError> {
+    if parts > Perbill::ACCURACY {
+        return Err(SaturnError::invalid_input(format!(
+            "{} is above the maximum of {} parts",
+            parts,
+            Perbill::ACCURACY
+        )));
+    }
+
+    Ok(Perbill::from_parts(parts))
+}
+
+/// A Perbill as a fraction between 0 and 1.
+fn perbill_fraction(perbill: Perbill) -> f64 {
+    perbill.deconstruct() as f64 / Perbill::ACCURACY as f64
+}
+
+/// The `author` RPC error code for a transaction the pool found invalid, with the runtime's
+/// `InvalidTransaction` as its data.
+const POOL_INVALID_TX: i64 = 1010;
+
+/// The `author` RPC error code for a transaction that doesn't outbid the pooled one it
+/// would replace, i.e. one using a nonce another pooled transaction already has.
+const POOL_TOO_LOW_PRIORITY: i64 = 1014;
+
+/// The `author` RPC error code for a transaction the pool dropped as soon as it was imported,
+/// the last of the pool's error codes starting at `POOL_INVALID_TX`.
+const POOL_IMMEDIATELY_DROPPED: i64 = 1016;
+
+/// The kind of a transaction pool rejection, going by the `author` RPC error code in `details`,
+/// a subxt RPC error's debug output. subxt only keeps jsonrpsee's error object there, so the
+/// code and data are read from it.
+///
+/// `InvalidTransaction::Stale`, for a nonce already used, `InvalidTransaction::Future`, for
+/// one ahead of the account's, and priority errors, for one another pooled transaction
+/// already has, are `StaleNonce`. Other pool errors are `InvalidTransaction`, or `Dropped`
+/// for an immediately dropped transaction.
+fn pool_error_kind(details: &str) -> Option<ErrorKind> {
+    let code = details
+        .split_once("ServerError(")
+        .and_then(|(_, rest)| rest.split(')').next())
+        .and_then(|code| code.parse::<i64>().ok())?;
+
+    match code {
+        POOL_INVALID_TX
+            if details.contains("Transaction is outdated")
+                || details.contains("Transaction will be valid in the future") =>
+        {
+            Some(ErrorKind::StaleNonce)
+        }
+        POOL_TOO_LOW_PRIORITY => Some(ErrorKind::StaleNonce),
+        POOL_IMMEDIATELY_DROPPED => Some(ErrorKind::Dropped),
+        POOL_INVALID_TX..=POOL_IMMEDIATELY_DROPPED => Some(ErrorKind::InvalidTransaction),
+        _ => None,
+    }
+}
+
+/// Whether `sign_and_submit` resubmits after `error` with the account's next nonce: only when
+/// asked to, for a stale nonce, and when the caller didn't pick the nonce themselves.
+fn retries_with_fresh_nonce(
+    retry_on_stale_nonce: bool,
+    nonce: Option<u64>,
+    error: &SaturnError,
+) -> bool {
+    retry_on_stale_nonce && nonce.is_none() && error.kind == ErrorKind::StaleNonce
+}
+
+/// Parses a decimal token amount, rejecting anything that doesn't fit in a `u128`.
+fn parse_amount(amount: &str) -> Result<u128, SaturnError> {
+    u128::from_str(amount)
+        .map_err(|e| SaturnError::invalid_input(format!("Invalid amount {}: {}", amount, e)))
+}
+
+/// Parses 0x-prefixed (or bare) hex encoded call data.
+fn parse_call_data(call_hex: &str) -> Result<Vec<u8>, SaturnError> {
+    hex::decode(call_hex.trim_start_matches("0x"))
+        .map_err(|e| SaturnError::invalid_input(format!("Invalid call data hex: {}", e)))
+}
+
+/// Parses a 0x-prefixed (or bare) hex string into a 32 byte hash.
+fn parse_hash(hash: &str) -> Result<H256, SaturnError> {
+    let bytes = hex::decode(hash.trim_start_matches("0x"))
+        .map_err(|e| SaturnError::invalid_input(format!("Invalid hash {}: {}", hash, e)))?;
+
+    let bytes: [u8; 32] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+        SaturnError::invalid_input(format!(
+            "Invalid hash {}: expected 32 bytes, got {}",
+            hash,
+            bytes.len()
+        ))
+    })?;
+
+    Ok(H256(bytes))
+}
+
+#[wasm_bindgen]
+pub struct Call {
+    api: OnlineClient<PolkadotConfig>,
+    call: Payload<Composite<()>>,
+    online: bool,
+    label: Option<String>,
+    event_pallets: Option<Vec<String>>,
+    find_core_created: bool,
+    timeout_ms: Option<u32>,
+    default_mortality: Option<u32>,
+    default_tip: Option<String>,
+}
+
+#[wasm_bindgen]
+impl Call {
+    /// Signs the call with `signer_function`, submits it and waits for it to be included.
+    ///
+    /// Resolves to a `SubmissionResult` once included, whose `core_created` carries the new
+    /// core's id when the call created a core. `block_hash` is the block the extrinsic was seen
+    /// in when it resolved.
+    ///
+    /// `options` sets mortality, tip, finality, nonce, a progress callback, stale nonce retries
+    /// and an abort signal, see `SubmitOptions`.
+    #[wasm_bindgen]
+    pub async fn sign_and_submit(
+        &self,
+        address: String,
+        signer_function: js_sys::Function,
+        options: Option<SubmitOptions>,
+    ) -> Result<SubmissionResult, SaturnError> {
+        self.ensure_online()?;
+
+        let options = options.unwrap_or_default();
+        let finality = options.finality.unwrap_or(Finality::InBlock);
+
+        let signed_extrinsic = self
+            .sign(
+                address.clone(),
+                signer_function.clone(),
+                options.mortality_period,
+                options.tip.clone(),
+                options.nonce,
+            )
+            .await?;
+
+        let error = match self
+            .submit(
+                signed_extrinsic,
+                finality,
+                options.progress_callback.as_ref(),
+                options.abort_signal.as_ref(),
+            )
+            .await
+        {
+            Err(error)
+                if retries_with_fresh_nonce(
+                    options.retry_on_stale_nonce,
+                    options.nonce,
+                    &error,
+                ) =>
+            {
+                error
+            }
+            result => return result,
+        };
+
+        utils::console_log!(label = self.label.as_deref(); "retrying with a fresh nonce: {}", error.message);
+
+        let account_id = AccountId32::from_str(&address)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let next_nonce = self
+            .api
+            .rpc()
+            .system_account_next_index(&account_id)
+            .await?;
+
+        let signed_extrinsic = self
+            .sign(
+                address,
+                signer_function,
+                options.mortality_period,
+                options.tip.clone(),
+                Some(next_nonce as u64),
+            )
+            .await?;
+
+        self.submit(
+            signed_extrinsic,
+            finality,
+            options.progress_callback.as_ref(),
+            options.abort_signal.as_ref(),
+        )
+        .await
+    }
+
+    /// The JSON signer payload for signing this call elsewhere, e.g. on a hardware wallet or
+    /// an air-gapped machine. Pass it with the signature to `submit_with_signature`.
+    ///
+    /// The payload carries the account's current nonce, so it has to be signed and submitted
+    /// before the account sends another transaction, unless `nonce` overrides it.
+    #[wasm_bindgen]
+    pub async fn build_payload(
+        &self,
+        address: String,
+        mortality_period: Option<u32>,
+        tip: Option<String>,
+        nonce: Option<u64>,
+    ) -> Result<String, SaturnError> {
+        self.signer_payload(address, mortality_period, tip, nonce)
+            .await
+    }
+
+    /// Submits the call with a hex encoded `MultiSignature` over `payload`, as returned by
+    /// `build_payload`. The extrinsic is built from the payload's address, nonce, era and tip,
+    /// so it matches what was signed. A raw signature can be passed instead, with `scheme`
+    /// set to `sr25519`, `ed25519` or `ecdsa`. `finality` and `abort_signal` work as in
+    /// `SubmitOptions`.
+    #[wasm_bindgen]
+    pub async fn submit_with_signature(
+        &self,
+        payload: String,
+        signature: String,
+        scheme: Option<String>,
+        finality: Option<Finality>,
+        abort_signal: Option<timeout::AbortSignal>,
+    ) -> Result<SubmissionResult, SaturnError> {
+        self.ensure_online()?;
+
+        let signed_extrinsic = self.apply_signature(&payload, &signature, scheme.as_deref())?;
+
+        self.submit(
+            signed_extrinsic,
+            finality.unwrap_or(Finality::InBlock),
+            None,
+            abort_signal.as_ref(),
+        )
+        .await
+    }
+
+    /// Signs the call and dry runs it against the latest block without submitting, returning
+    /// `{ success, failure, error }`. `failure` is `"invalid_transaction"` when the transaction
+    /// itself would be rejected, e.g. for a bad nonce or insufficient balance for fees, and
+    /// `"dispatch"` when it would be included but the call fails, with the error in `error`.
+    ///
+    /// The dry run needs a valid signature, so `signer_function` is asked to sign just like in
+    /// `sign_and_submit`.
+    #[wasm_bindgen]
+    pub async fn dry_run(
+        &self,
+        address: String,
+        signer_function: js_sys::Function,
+    ) -> Result<JsValue, SaturnError> {
+        self.ensure_online()?;
+
+        let signed_extrinsic = self
+            .sign(address, signer_function, None, None, None)
+            .await?;
+
+        let result = signed_extrinsic.dry_run(None).await?;
+
+        let outcome = match result {
+            DryRunResult::Success => DryRunOutcome {
+                success: true,
+                failure: None,
+                error: None,
+            },
+            DryRunResult::DispatchError(error) => DryRunOutcome {
+                success: false,
+                failure: Some(String::from("dispatch")),
+                error: Some(error.to_string()),
+            },
+            DryRunResult::TransactionValidityError => DryRunOutcome {
+                success: false,
+                failure: Some(String::from("invalid_transaction")),
+                error: None,
+            },
+        };
+
+        serde_wasm_bindgen::to_value(&outcome).map_err(|e| SaturnError::encoding(e.to_string()))
+    }
+
+    /// Estimates the fee for submitting this call, as a decimal amount of `fee_asset`, TNKR by
+    /// default. The estimate comes from `TransactionPaymentApi_query_info` for the call signed
+    /// with a dummy signature, so no signer is needed.
+    ///
+    /// Transaction fees are always paid in TNKR and the runtime has no exchange rate to convert
+    /// them, so for KSM only `create_core` has a figure: INV4's `KSMCoreCreationFee`, which it
+    /// charges on top of the TNKR transaction fee. For other calls, and other fee assets, the
+    /// estimate is undefined.
+    ///
+    /// For `operate_multisig` this is only the submitter's fee. The fee for the inner call is
+    /// charged to the core account in the proposal's `fee_asset` when it executes.
+    #[wasm_bindgen]
+    pub async fn estimate_fee(
+        &self,
+        fee_asset: Option<JsFeeAsset>,
+    ) -> Result<Option<String>, SaturnError> {
+        let fee_asset = fee_asset.unwrap_or_else(JsFeeAsset::tnkr);
+
+        let fee = if fee_asset.is_tnkr() {
+            self.fee_estimate().await?
+        } else if fee_asset.is_ksm() && self.is_create_core() {
+            self.api
+                .constants()
+                .at(&tinkernet::constants().inv4().ksm_core_creation_fee())?
+        } else {
+            return Ok(None);
+        };
+
+        Ok(Some(fee.to_string()))
+    }
+
+    /// Whether `address` has enough free balance to submit this call: the estimated fee plus
+    /// the existential deposit in TNKR, which transaction fees are always paid in.
+    ///
+    /// `fee_asset` should be the one the call was built with. For `create_core`, the creation
+    /// fee is charged to the signer in that asset, so their balance of it is checked too. For
+    /// `operate_multisig` proposals, INV4 charges the proposed call's fee to the core account
+    /// in `fee_asset` when it executes, so the core's balance of it is checked: against the
+    /// proposed call's estimated fee plus the existential deposit for TNKR, and only for being
+    /// non-zero for KSM, as the runtime has no exchange rate to convert the fee with.
+    #[wasm_bindgen]
+    pub async fn can_afford(
+        &self,
+        address: String,
+        fee_asset: &JsFeeAsset,
+    ) -> Result<bool, SaturnError> {
+        let label = self.label.as_deref();
+
+        let account_id = AccountId32::from_str(&address)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
+
+        let constants = self.api.constants();
+        let inv4 = tinkernet::constants().inv4();
+
+        let existential_deposit =
+            constants.at(&tinkernet::constants().balances().existential_deposit())?;
+
+        let mut tnkr_needed = self
+            .fee_estimate()
+            .await?
+            .saturating_add(existential_deposit);
+
+        // Balances needed in `fee_asset` besides the signer's TNKR, by account.
+        let mut fee_asset_needed = Vec::new();
+
+        if self.is_create_core() {
+            if fee_asset.is_tnkr() {
+                let creation_fee = constants.at(&inv4.core_creation_fee())?;
+                tnkr_needed = tnkr_needed.saturating_add(creation_fee);
+            } else if fee_asset.is_ksm() {
+                let creation_fee = constants.at(&inv4.ksm_core_creation_fee())?;
+                fee_asset_needed.push((account_id.clone(), creation_fee));
+            } else {
+                return Err(SaturnError::invalid_input(format!(
+                    "No known core creation fee in {}",
+                    fee_asset.name
+                )));
+            }
+        } else if let Some((core_id, proposed_call)) = multisig_proposal(&self.call) {
+            let needed = if fee_asset.is_tnkr() {
+                fee_estimate(&self.api, &proposed_call)
+                    .await?
+                    .saturating_add(existential_deposit)
+            } else {
+                1
+            };
+
+            fee_asset_needed.push((core_account(&self.api, core_id)?, needed));
         }
-    }
-}
 
-#[wasm_bindgen]
-pub struct CoreCreationResult {
-    core_id: u32,
-}
+        let storage = storage_at(&self.api, self.timeout_ms, None).await?;
 
-#[wasm_bindgen]
-pub struct Saturn {
-    api: OnlineClient<PolkadotConfig>,
-}
+        let tnkr_free =
+            fee_asset_balance(&self.api, &storage, &account_id, &JsFeeAsset::tnkr()).await?;
 
-#[wasm_bindgen]
-impl Saturn {
-    #[wasm_bindgen(constructor)]
-    pub async fn new(url: String) -> Result<Saturn, SaturnError> {
-        utils::set_panic_hook();
+        if tnkr_free < tnkr_needed {
+            utils::console_log!(label = label; "{} has {} TNKR free, needs {}", address, tnkr_free, tnkr_needed);
+            return Ok(false);
+        }
 
-        let api = OnlineClient::<PolkadotConfig>::from_url(url)
-            .await
-            .map_err(|e| SaturnError::from(e))?;
+        for (account, needed) in fee_asset_needed {
+            let free = fee_asset_balance(&self.api, &storage, &account, fee_asset).await?;
+
+            if free < needed {
+                utils::console_log!(label = label; "{} has {} {} free, needs {}", ss58_address(&self.api, &account), free, fee_asset.name, needed);
+                return Ok(false);
+            }
+        }
 
-        return Ok(Self { api });
+        Ok(true)
     }
 
+    /// Restricts the events `sign_and_submit` decodes and logs to those of `pallets`, e.g.
+    /// `["INV4", "System"]`, which makes submission faster on busy blocks. Single events can be
+    /// picked as `Pallet.Event`, e.g. `["INV4.CoreCreated"]`. All events are logged by default.
     #[wasm_bindgen]
-    pub fn create_core(
-        &self,
-        metadata: String,
-        minimum_support: u32,
-        required_approval: u32,
-        fee_asset: JsFeeAsset,
-    ) -> Call {
-        let dcd = subxt::dynamic::tx(
-            "INV4",
-            "create_core",
-            vec![
-                (
-                    "metadata",
-                    Value::from_bytes(
-                        tinkernet::runtime_types::bounded_collections::bounded_vec::BoundedVec(
-                            metadata.as_bytes().to_vec(),
-                        )
-                        .encode(),
-                    ),
-                ),
-                (
-                    "minimum_support",
-                    Value::unnamed_composite([Value::u128(
-                        Perbill::from_parts(minimum_support).deconstruct() as u128,
-                    )]),
-                ),
-                (
-                    "required_approval",
-                    Value::unnamed_composite([Value::u128(
-                        Perbill::from_parts(required_approval).deconstruct() as u128,
-                    )]),
-                ),
-                (
-                    "creation_fee_asset",
-                    Value::unnamed_variant(
-                        match fee_asset {
-                            JsFeeAsset::TNKR => "TNKR",
-                            JsFeeAsset::KSM => "KSM",
-                        },
-                        [],
-                    ),
-                ),
-            ],
-        );
+    pub fn with_event_pallets(mut self, pallets: Vec<String>) -> Call {
+        self.event_pallets = Some(pallets);
+        self
+    }
 
-        Call {
-            api: self.api.clone(),
-            call: dcd.clone(),
-        }
+    /// Skips looking for a `CoreCreated` event after submission, for calls that can't create a
+    /// core. `SubmissionResult.core_created` is then always null.
+    #[wasm_bindgen]
+    pub fn without_core_created(mut self) -> Call {
+        self.find_core_created = false;
+        self
     }
 
+    /// The encoded call data as unpadded base64url, compact enough for links and QR codes.
+    /// `Saturn.call_from_compact` turns it back into a `Call`.
     #[wasm_bindgen]
-    pub async fn get_voting_balance(
-        &self,
-        core_id: u32,
-        account: String,
-    ) -> Result<String, SaturnError> {
-        let account_id = AccountId32::from_str(&account).unwrap();
+    pub fn to_compact(&self) -> Result<String, SaturnError> {
+        let call_data = self.api.tx().call_data(&self.call)?;
 
-        let storage_query = tinkernet::storage()
-            .core_assets()
-            .accounts(account_id, core_id);
+        Ok(URL_SAFE_NO_PAD.encode(call_data))
+    }
 
-        let result = self
-            .api
-            .storage()
-            .at_latest()
-            .await
-            .map_err(|e| SaturnError::from(e))?
-            .fetch(&storage_query)
-            .await
-            .map_err(|e| SaturnError::from(e))?;
+    /// blake2_256 hash of the encoded call data, as hex. Passed to `operate_multisig`, this is
+    /// the `call_hash` INV4 keys the proposal on, and reports in its `Multisig*` events.
+    #[wasm_bindgen]
+    pub fn call_hash(&self) -> Result<String, SaturnError> {
+        let call_data = self.api.tx().call_data(&self.call)?;
 
-        return Ok(result.unwrap().free.to_string());
+        Ok(js_signer::to_hex(sp_core_hashing::blake2_256(&call_data)))
     }
-}
 
-#[wasm_bindgen]
-pub struct Call {
-    api: OnlineClient<PolkadotConfig>,
-    call: Payload<Composite<()>>,
+    /// The exact bytes `address` has to sign for this call: the SCALE encoded call data, signed
+    /// extensions' extra and additional signed data. `mortality_period`, `tip` and `nonce` work
+    /// as in `build_payload`, and the bytes are those of its payload, so a signature over them
+    /// can be passed to `submit_with_signature` with that payload.
+    ///
+    /// Payloads longer than 256 bytes are signed as their blake2_256 hash, so for those the
+    /// 32 byte hash is returned instead, which is what the runtime verifies the signature against.
+    #[wasm_bindgen]
+    pub async fn signer_payload_bytes(
+        &self,
+        address: String,
+        mortality_period: Option<u32>,
+        tip: Option<String>,
+        nonce: Option<u64>,
+    ) -> Result<js_sys::Uint8Array, SaturnError> {
+        let payload = self
+            .signer_payload(address, mortality_period, tip, nonce)
+            .await?;
+
+        let partial_signed = self.partial_signed(&js_signer::parse_payload(&payload)?)?;
+
+        Ok(js_sys::Uint8Array::from(
+            partial_signed.signer_payload().as_slice(),
+        ))
+    }
 }
 
-#[wasm_bindgen]
 impl Call {
-    #[wasm_bindgen]
-    pub async fn sign_and_submit(
+    /// Whether the call is `INV4.create_core`, whose creation fee depends on its `fee_asset`.
+    fn is_create_core(&self) -> bool {
+        self.call.pallet_name() == "INV4" && self.call.call_name() == "create_core"
+    }
+
+    /// Rejects submitting calls built by a `Saturn::new_offline` client.
+    fn ensure_online(&self) -> Result<(), SaturnError> {
+        if !self.online {
+            return Err(SaturnError::invalid_input(String::from(
+                "Call was built offline, rebuild it with a connected client's call_from_bytes to submit it",
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// `TransactionPayment` fee estimate for the call, in TNKR.
+    async fn fee_estimate(&self) -> Result<u128, SaturnError> {
+        fee_estimate(&self.api, &self.call).await
+    }
+
+    /// The `generate_payload` JSON payload `address` has to sign for this call. Falls back to
+    /// the client's default mortality and tip when they're not given.
+    async fn signer_payload(
         &self,
         address: String,
-        signer_function: js_sys::Function,
-    ) -> Result<(), SaturnError> {
+        mortality_period: Option<u32>,
+        tip: Option<String>,
+        nonce: Option<u64>,
+    ) -> Result<String, SaturnError> {
+        let label = self.label.as_deref();
+
+        let mortality_period = mortality_period.or(self.default_mortality);
+        let tip = tip.or_else(|| self.default_tip.clone());
+
+        let tip = tip.as_deref().map(parse_amount).transpose()?.unwrap_or(0);
+
         let account_id = AccountId32::from_str(&address).map_err(|e| {
-            utils::console_log!("rust account_id error: {:?}", e);
-            SaturnError(e.to_string())
+            utils::console_log!(label = label; "rust account_id error: {:?}", e);
+            SaturnError::invalid_input(e.to_string())
         })?;
 
         let call_data = self.api.tx().call_data(&self.call).map_err(|e| {
-            utils::console_log!("rust call_data error: {:?}", e);
-            SaturnError(String::from("could not encode call data"))
+            utils::console_log!(label = label; "rust call_data error: {:?}", e);
+            SaturnError::encoding(String::from("could not encode call data"))
         })?;
 
-        let account_nonce = self
-            .api
-            .tx()
-            .account_nonce(&account_id)
-            .await
-            .map_err(|e| {
-                utils::console_log!("rust account_nonce error: {:?}", e);
-                SaturnError(String::from("Fetching account nonce failed"))
-            })?;
+        let account_nonce = match nonce {
+            Some(nonce) => nonce,
+            None => self
+                .api
+                .tx()
+                .account_nonce(&account_id)
+                .await
+                .map_err(|e| {
+                    utils::console_log!(label = label; "rust account_nonce error: {:?}", e);
+                    SaturnError::network(String::from("Fetching account nonce failed"))
+                })?,
+        };
+
+        let mortality = match mortality_period {
+            Some(period) => {
+                Some(js_signer::Mortality::from_best_block(&self.api, period as u64).await?)
+            }
+            None => None,
+        };
 
-        let payload =
-            js_signer::generate_payload(&self.api, address, account_nonce, call_data).await;
+        js_signer::generate_payload(
+            &self.api,
+            address,
+            account_nonce,
+            call_data,
+            mortality.as_ref(),
+            tip,
+        )
+    }
+
+    /// Builds the signer payload, has `signer_function` sign it and applies the signature.
+    async fn sign(
+        &self,
+        address: String,
+        signer_function: js_sys::Function,
+        mortality_period: Option<u32>,
+        tip: Option<String>,
+        nonce: Option<u64>,
+    ) -> Result<SignedExtrinsic, SaturnError> {
+        let label = self.label.as_deref();
+
+        let payload = self
+            .signer_payload(address, mortality_period, tip, nonce)
+            .await?;
 
         let this = JsValue::null();
         let signature_future = signer_function
-            .call1(&this, &js_signer::json_parse(payload))
+            .call1(&this, &js_signer::json_parse(payload.clone()))
             .map_err(|e| {
-                utils::console_log!("rust signature call1 error: {:?}", e);
-                SaturnError(format!("{:?}", e))
+                utils::console_log!(label = label; "rust signature call1 error: {:?}", e);
+                SaturnError::signature(format!("{:?}", e))
             })?;
 
         let signature =
             wasm_bindgen_futures::JsFuture::from(js_sys::Promise::resolve(&signature_future))
                 .await
                 .map_err(|e| {
-                    utils::console_log!("rust signature await {:?}", e);
-                    SaturnError(format!("rust signature await {:?}", e))
+                    utils::console_log!(label = label; "rust signature await {:?}", e);
+                    if js_signer::is_user_rejection(&e) {
+                        SaturnError::user_rejected(String::from("Signing was cancelled"))
+                    } else {
+                        SaturnError::signature(format!("rust signature await {:?}", e))
+                    }
                 })?;
 
         let signature_response: SignatureResponse = serde_wasm_bindgen::from_value(signature)
-            .map_err(|_| SaturnError(String::from("Error deserializing SignatureResponse")))?;
+            .map_err(|_| {
+                SaturnError::signature(String::from("Error deserializing SignatureResponse"))
+            })?;
+
+        self.apply_signature(
+            &payload,
+            &signature_response.signature,
+            signature_response.scheme.as_deref(),
+        )
+    }
+
+    /// Builds the extrinsic `payload` describes and applies its hex encoded signature, see
+    /// `js_signer::multi_signature`.
+    fn apply_signature(
+        &self,
+        payload: &str,
+        signature: &str,
+        scheme: Option<&str>,
+    ) -> Result<SignedExtrinsic, SaturnError> {
+        let label = self.label.as_deref();
+
+        let payload = js_signer::parse_payload(payload)?;
 
-        let signature = signature_response.signature;
+        let account_id = AccountId32::from_str(&payload.address)
+            .map_err(|e| SaturnError::invalid_input(e.to_string()))?;
 
-        let signature = hex::decode(&signature[2..]).map_err(|e| SaturnError(e.to_string()))?;
+        let signature = hex::decode(signature.trim_start_matches("0x"))
+            .map_err(|e| SaturnError::signature(e.to_string()))?;
 
-        let multi_signature = MultiSignature::decode(&mut &signature[..]).map_err(|e| {
-            utils::console_log!("rust multi_signature error: {:?}", e);
-            SaturnError(String::from("MultiSignature Decoding"))
+        let multi_signature = js_signer::multi_signature(&signature, scheme).map_err(|e| {
+            utils::console_log!(label = label; "rust multi_signature error: {:?}", e.inner());
+            e
         })?;
 
-        let partial_signed = self
-            .api
-            .tx()
-            .create_partial_signed_with_nonce(&self.call, account_nonce, Default::default())
-            .map_err(|e| {
-                utils::console_log!("rust partial_signed error: {:?}", e);
-                SaturnError(format!("PartialExtrinsic creation failed. Error: {:?}", e))
-            })?;
+        let partial_signed = self.partial_signed(&payload)?;
 
         // Apply the signature
-        let signed_extrinsic =
-            partial_signed.sign_with_address_and_signature(&account_id.into(), &multi_signature);
+        Ok(partial_signed.sign_with_address_and_signature(&account_id.into(), &multi_signature))
+    }
 
-        let result = js_signer::submit_wait_inblock_and_get_event(signed_extrinsic)
-            .await
+    /// The unsigned extrinsic `payload` describes, built with the nonce, era, checkpoint and tip
+    /// the signer signs over.
+    fn partial_signed(
+        &self,
+        payload: &js_signer::SignedPayload,
+    ) -> Result<PartialExtrinsic<PolkadotConfig, OnlineClient<PolkadotConfig>>, SaturnError> {
+        let label = self.label.as_deref();
+
+        let params = PolkadotExtrinsicParamsBuilder::new()
+            .tip(PlainTip::new(payload.tip))
+            .era(payload.era, payload.checkpoint_hash);
+
+        self.api
+            .tx()
+            .create_partial_signed_with_nonce(&self.call, payload.nonce, params)
             .map_err(|e| {
-                utils::console_log!("rust result error: {:?}", e);
-                return SaturnError(e.to_string());
-            })?;
+                utils::console_log!(label = label; "rust partial_signed error: {:?}", e);
+                SaturnError::encoding(format!("PartialExtrinsic creation failed. Error: {:?}", e))
+            })
+    }
 
-        utils::console_log!("rust result: {:?}", result);
+    /// Submits a signed extrinsic and collects its `SubmissionResult`.
+    async fn submit(
+        &self,
+        signed_extrinsic: SignedExtrinsic,
+        finality: Finality,
+        on_progress: Option<&js_sys::Function>,
+        abort_signal: Option<&timeout::AbortSignal>,
+    ) -> Result<SubmissionResult, SaturnError> {
+        let label = self.label.as_deref();
 
-        Ok(())
+        let result = timeout::until_aborted(
+            abort_signal,
+            "Submission",
+            timeout::with_timeout(
+                self.timeout_ms,
+                "Submission",
+                js_signer::submit_and_collect_events(
+                    &self.api.metadata(),
+                    label,
+                    self.event_pallets.as_deref(),
+                    finality,
+                    on_progress,
+                    signed_extrinsic,
+                ),
+            ),
+        )
+        .await???;
+
+        let core_created = if self.find_core_created {
+            result.core_created().map_err(|e| {
+                utils::console_log!(label = label; "rust core_created error: {:?}", e);
+                SaturnError::encoding(e.to_string())
+            })?
+        } else {
+            None
+        };
+
+        if let Some(core_created) = &core_created {
+            utils::console_log!(label = label; "core_created_event: {:#?}", core_created);
+        }
+
+        let predicted_state = predicted_changes(&self.api, &result.events)?;
+
+        Ok(SubmissionResult {
+            tx_hash: js_signer::to_hex(result.tx_hash),
+            block_hash: js_signer::to_hex(result.block_hash),
+            error: result.error,
+            batch_failure: result.batch_failure,
+            events: result.described_events,
+            core_created: core_created.map(|core_created| CoreCreationResult {
+                core_id: core_created.core_id,
+                core_account: ss58_address(&self.api, &core_created.core_account),
+                metadata: display_bytes(&core_created.metadata),
+                minimum_support: perbill_fraction(core_created.minimum_support.0),
+                required_approval: perbill_fraction(core_created.required_approval.0),
+            }),
+            predicted_state,
+        })
     }
 }
 
@@ -333,3 +4750,536 @@ impl Call {
 //         Ok(())
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tinkernet::runtime_types::{
+        orml_tokens::module::Call as TokensCall, pallet_inv4::pallet::Call as InvCall,
+        sp_runtime::multiaddress::MultiAddress, tinkernet_runtime::RuntimeCall,
+    };
+
+    /// Tinkernet's genesis hash, which the runtime also uses as `INV4.GenesisHash`.
+    const GENESIS_HASH: &str = "0xd42e9606a995dfe433dc7955dc2a70f495f350f373daa200098ae84437816ad2";
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    /// Client over the bundled `tinkernet.scale`, spec version 19.
+    fn offline_saturn() -> Saturn {
+        Saturn::new_offline(
+            include_bytes!("../tinkernet.scale").to_vec(),
+            String::from(GENESIS_HASH),
+            19,
+            1,
+            None,
+        )
+        .unwrap()
+    }
+
+    fn call_data(call: &Call) -> Vec<u8> {
+        call.api.tx().call_data(&call.call).unwrap()
+    }
+
+    #[test]
+    fn bundled_metadata_decodes() {
+        let metadata = Metadata::decode(&mut &include_bytes!("../tinkernet.scale")[..]).unwrap();
+
+        assert!(metadata.pallet_by_name("INV4").is_some());
+    }
+
+    #[test]
+    fn bundled_metadata_has_required_calls() {
+        let metadata = Metadata::decode(&mut &include_bytes!("../tinkernet.scale")[..]).unwrap();
+
+        for (pallet, call) in REQUIRED_CALLS {
+            assert!(
+                metadata
+                    .pallet_by_name(pallet)
+                    .and_then(|metadata| metadata.call_variant_by_name(call))
+                    .is_some(),
+                "{}.{} missing",
+                pallet,
+                call
+            );
+        }
+    }
+
+    #[test]
+    fn multisig_proposal_reads_back_the_proposed_call() {
+        let saturn = offline_saturn();
+
+        let transfer = saturn
+            .token_transfer(0, String::from("1"), String::from(ALICE))
+            .unwrap();
+        let proposal = saturn
+            .operate_multisig(3, None, &JsFeeAsset::ksm(), &transfer, None)
+            .unwrap();
+
+        let (core_id, proposed_call) = multisig_proposal(&proposal.call).unwrap();
+
+        assert_eq!(core_id, 3);
+        assert_eq!(
+            saturn.api.tx().call_data(&proposed_call).unwrap(),
+            call_data(&transfer)
+        );
+        assert!(multisig_proposal(&transfer.call).is_none());
+    }
+
+    #[test]
+    fn call_hash_is_the_hash_inv4_keys_proposals_on() {
+        let saturn = offline_saturn();
+
+        let transfer = saturn
+            .token_transfer(0, String::from("1"), String::from(ALICE))
+            .unwrap();
+        let proposal = saturn
+            .operate_multisig(3, None, &JsFeeAsset::tnkr(), &transfer, None)
+            .unwrap();
+
+        let RuntimeCall::INV4(InvCall::operate_multisig { call, .. }) =
+            RuntimeCall::decode(&mut &call_data(&proposal)[..]).unwrap()
+        else {
+            panic!("expected INV4.operate_multisig");
+        };
+
+        // INV4 hashes the boxed `RuntimeCall` with `T::Hashing::hash_of`, blake2_256 of its
+        // encoding.
+        assert_eq!(
+            transfer.call_hash().unwrap(),
+            js_signer::to_hex(sp_core_hashing::blake2_256(&call.encode()))
+        );
+    }
+
+    #[test]
+    fn fee_assets_are_looked_up_in_metadata() {
+        let saturn = offline_saturn();
+
+        assert_eq!(
+            saturn.fee_asset(String::from("ksm")).unwrap(),
+            JsFeeAsset::ksm()
+        );
+        assert_eq!(
+            saturn.fee_asset(String::from("TNKR")).unwrap(),
+            JsFeeAsset::tnkr()
+        );
+
+        let error = saturn.fee_asset(String::from("DOT")).err().unwrap();
+        assert_eq!(error.kind, ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn ksm_fee_estimate_is_only_the_core_creation_fee() {
+        let saturn = offline_saturn();
+
+        let create_core = saturn
+            .create_core(
+                String::from("core"),
+                500_000_000,
+                500_000_000,
+                &JsFeeAsset::ksm(),
+            )
+            .unwrap();
+        assert_eq!(
+            futures::executor::block_on(create_core.estimate_fee(Some(JsFeeAsset::ksm()))).unwrap(),
+            Some(String::from("1000000000000"))
+        );
+
+        let transfer = saturn
+            .token_transfer(0, String::from("1"), String::from(ALICE))
+            .unwrap();
+        assert_eq!(
+            futures::executor::block_on(transfer.estimate_fee(Some(JsFeeAsset::ksm()))).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn max_metadata_length_is_runtime_constant() {
+        assert_eq!(offline_saturn().max_metadata_length().unwrap(), 10_000);
+    }
+
+    #[test]
+    fn create_core_checks_metadata_length() {
+        let saturn = offline_saturn();
+        let create_core = |metadata: String| {
+            saturn.create_core(metadata, 500_000_000, 500_000_000, &JsFeeAsset::tnkr())
+        };
+
+        assert!(create_core("a".repeat(10_000)).is_ok());
+
+        let error = create_core("a".repeat(10_001)).err().unwrap();
+        assert_eq!(error.kind, ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn create_core_encodes_metadata_as_given() {
+        let call = offline_saturn()
+            .create_core(
+                String::from(r#"{"name":"core"}"#),
+                500_000_000,
+                500_000_000,
+                &JsFeeAsset::tnkr(),
+            )
+            .unwrap();
+
+        let RuntimeCall::INV4(InvCall::create_core { metadata, .. }) =
+            RuntimeCall::decode(&mut &call_data(&call)[..]).unwrap()
+        else {
+            panic!("expected INV4.create_core");
+        };
+
+        assert_eq!(metadata.0, br#"{"name":"core"}"#.to_vec());
+    }
+
+    #[test]
+    fn token_burn_is_proposed_to_the_core() {
+        let metadata = Metadata::decode(&mut &include_bytes!("../tinkernet.scale")[..]).unwrap();
+
+        let call = offline_saturn()
+            .token_burn(
+                3,
+                String::from("1000"),
+                String::from(ALICE),
+                &JsFeeAsset::tnkr(),
+            )
+            .unwrap();
+
+        let RuntimeCall::INV4(InvCall::operate_multisig { core_id, call, .. }) =
+            RuntimeCall::decode(&mut &call_data(&call)[..]).unwrap()
+        else {
+            panic!("expected INV4.operate_multisig");
+        };
+        assert_eq!(core_id, 3);
+
+        let burn = call_decoding::decode_call(&metadata, &call.encode()).unwrap();
+        assert_eq!(burn.pallet, "INV4");
+        assert_eq!(burn.call, "token_burn");
+
+        let RuntimeCall::INV4(InvCall::token_burn { amount, target }) = *call else {
+            panic!("expected INV4.token_burn");
+        };
+        assert_eq!(amount, 1000);
+        assert_eq!(target, AccountId32::from_str(ALICE).unwrap());
+    }
+
+    #[test]
+    fn multisig_payload_checks_every_proposal() {
+        let saturn = offline_saturn();
+
+        let transfer = saturn
+            .token_transfer(0, String::from("1"), String::from(ALICE))
+            .unwrap();
+        let proposal = saturn
+            .operate_multisig(3, None, &JsFeeAsset::tnkr(), &transfer, None)
+            .unwrap();
+
+        let error = multisig_payload(&saturn.api, 3, None, &JsFeeAsset::tnkr(), &proposal, false)
+            .err()
+            .unwrap();
+        assert_eq!(error.kind, ErrorKind::InvalidInput);
+        assert!(
+            multisig_payload(&saturn.api, 3, None, &JsFeeAsset::tnkr(), &proposal, true).is_ok()
+        );
+
+        let max_call_size = saturn
+            .api
+            .constants()
+            .at(&tinkernet::constants().inv4().max_call_size())
+            .unwrap();
+        let remark = saturn.new_call(subxt::dynamic::tx(
+            "System",
+            "remark",
+            vec![Value::from_bytes(vec![0; max_call_size as usize])],
+        ));
+
+        let error = multisig_payload(&saturn.api, 3, None, &JsFeeAsset::tnkr(), &remark, false)
+            .err()
+            .unwrap();
+        assert_eq!(error.kind, ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn vote_multisig_encodes_the_vote() {
+        let call_hash = H256([7; 32]);
+
+        let call = offline_saturn()
+            .vote_multisig(3, js_signer::to_hex(call_hash), true)
+            .unwrap();
+
+        let RuntimeCall::INV4(InvCall::vote_multisig {
+            core_id,
+            call_hash: voted_hash,
+            aye,
+        }) = RuntimeCall::decode(&mut &call_data(&call)[..]).unwrap()
+        else {
+            panic!("expected INV4.vote_multisig");
+        };
+
+        assert_eq!(core_id, 3);
+        assert_eq!(voted_hash, call_hash);
+        assert!(aye);
+    }
+
+    #[test]
+    fn token_transfer_round_trips() {
+        let saturn = offline_saturn();
+
+        let call = saturn
+            .token_transfer(3, String::from("1000"), String::from(ALICE))
+            .unwrap();
+        let data = call_data(&call);
+
+        let RuntimeCall::CoreAssets(TokensCall::transfer {
+            dest: MultiAddress::Id(dest),
+            currency_id,
+            amount,
+        }) = RuntimeCall::decode(&mut &data[..]).unwrap()
+        else {
+            panic!("expected CoreAssets.transfer");
+        };
+        assert_eq!(dest, AccountId32::from_str(ALICE).unwrap());
+        assert_eq!(currency_id, 3);
+        assert_eq!(amount, 1000);
+
+        let decoded = saturn.call_from_bytes(js_signer::to_hex(&data)).unwrap();
+        assert_eq!(call_data(&decoded), data);
+    }
+
+    #[test]
+    fn token_transfer_rejects_zero_amount() {
+        let error = offline_saturn()
+            .token_transfer(3, String::from("0"), String::from(ALICE))
+            .err()
+            .unwrap();
+
+        assert_eq!(error.kind, ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn payload_tip_is_the_tip_signed_over() {
+        let call = offline_saturn()
+            .token_transfer(0, String::from("1"), String::from(ALICE))
+            .unwrap();
+
+        let payload = js_signer::generate_payload(
+            &call.api,
+            String::from(ALICE),
+            5,
+            call_data(&call),
+            None,
+            1_000_000,
+        )
+        .unwrap();
+
+        // Rebuilds the bytes an extension signs from the JSON fields, the way polkadot-js does.
+        let json: serde_json::Value = serde_json::from_str(&payload).unwrap();
+        let field = |name: &str| {
+            hex::decode(json[name].as_str().unwrap().trim_start_matches("0x")).unwrap()
+        };
+        let nonce = u64::from_be_bytes(field("nonce").try_into().unwrap());
+        let spec_version = u32::from_be_bytes(field("specVersion").try_into().unwrap());
+        let transaction_version =
+            u32::from_be_bytes(field("transactionVersion").try_into().unwrap());
+
+        let mut signed = field("method");
+        signed.extend(field("era"));
+        signed.extend(Compact(nonce).encode());
+        signed.extend(field("tip"));
+        signed.extend(spec_version.encode());
+        signed.extend(transaction_version.encode());
+        signed.extend(field("genesisHash"));
+        signed.extend(field("blockHash"));
+
+        let partial_signed = call
+            .partial_signed(&js_signer::parse_payload(&payload).unwrap())
+            .unwrap();
+
+        assert_eq!(field("tip"), Compact(1_000_000u128).encode());
+        assert_eq!(partial_signed.signer_payload(), signed);
+    }
+
+    #[test]
+    fn derive_core_account_matches_runtime() {
+        let saturn = offline_saturn();
+
+        assert_eq!(
+            saturn.derive_core_account(0).unwrap(),
+            AccountId32::from_str("5EGiKEyeNwAr5qu5XVKYg6bAPx7A1Myw6YDxZHdQJjeV8Xzd").unwrap()
+        );
+        assert_eq!(
+            saturn.get_core_account(1).unwrap(),
+            "i53NKysKfN4EwLwPN7AzuriNXzdV78uN7uGCTV3RoViHsMszU"
+        );
+    }
+
+    #[test]
+    fn key_suffixes_reject_short_keys() {
+        let mut key = map_prefix("INV4", "CoreMembers", &7u32);
+        key.extend(sp_core_hashing::blake2_128(&[1; 32]));
+        key.extend([1; 32]);
+
+        assert_eq!(
+            key_suffix_account(&key, "CoreMembers").unwrap(),
+            AccountId32([1; 32])
+        );
+        assert_eq!(key_suffix_u32(&7u32.encode(), "CoreStorage").unwrap(), 7);
+
+        assert!(key_suffix_account(&[0; 31], "CoreMembers").is_err());
+        assert!(key_suffix_u32(&[0; 3], "CoreStorage").is_err());
+        assert!(key_suffix_u32(&[], "CoreStorage").is_err());
+    }
+
+    #[test]
+    fn parse_perbill_bounds() {
+        assert_eq!(parse_perbill(0).unwrap(), Perbill::zero());
+        assert_eq!(parse_perbill(1_000_000_000).unwrap(), Perbill::one());
+
+        let error = parse_perbill(1_000_000_001).err().unwrap();
+        assert_eq!(error.kind, ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn perbill_from_fraction_bounds() {
+        assert_eq!(perbill_from_fraction(0.0).unwrap(), 0);
+        assert_eq!(perbill_from_fraction(0.5).unwrap(), 500_000_000);
+        assert_eq!(perbill_from_fraction(1.0).unwrap(), 1_000_000_000);
+
+        let error = perbill_from_fraction(1.000_000_001).err().unwrap();
+        assert_eq!(error.kind, ErrorKind::InvalidInput);
+    }
+
+    /// jsonrpsee's error for an `author` RPC call the pool rejected, as subxt boxes it.
+    #[derive(Clone, Copy)]
+    struct PoolRejection {
+        code: i64,
+        message: &'static str,
+        data: &'static str,
+    }
+
+    impl std::fmt::Debug for PoolRejection {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "Call(Custom(ErrorObject {{ code: ServerError({}), message: {:?}, data: Some(RawValue({:?})) }}))",
+                self.code, self.message, self.data
+            )
+        }
+    }
+
+    impl std::fmt::Display for PoolRejection {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "RPC call failed: {:?}", self)
+        }
+    }
+
+    impl std::error::Error for PoolRejection {}
+
+    /// RPC client whose node rejects every request, including submissions, with `rejection`.
+    struct RejectingRpcClient(PoolRejection);
+
+    impl subxt::rpc::RpcClientT for RejectingRpcClient {
+        fn request_raw<'a>(
+            &'a self,
+            _method: &'a str,
+            _params: Option<Box<serde_json::value::RawValue>>,
+        ) -> subxt::rpc::RpcFuture<'a, Box<serde_json::value::RawValue>> {
+            let rejection = self.0;
+            Box::pin(async move { Err(subxt::error::RpcError::ClientError(Box::new(rejection))) })
+        }
+
+        fn subscribe_raw<'a>(
+            &'a self,
+            _sub: &'a str,
+            _params: Option<Box<serde_json::value::RawValue>>,
+            _unsub: &'a str,
+        ) -> subxt::rpc::RpcFuture<'a, subxt::rpc::RpcSubscription> {
+            let rejection = self.0;
+            Box::pin(async move { Err(subxt::error::RpcError::ClientError(Box::new(rejection))) })
+        }
+    }
+
+    /// Submits a `create_core` call, signed with a dummy signature, to a node rejecting it with
+    /// `rejection`, and returns the error `Call::submit` fails with.
+    fn rejected_submission(rejection: PoolRejection) -> SaturnError {
+        let mut saturn = offline_saturn();
+        saturn.online = true;
+        saturn.api = OnlineClient::<PolkadotConfig>::from_rpc_client_with(
+            parse_hash(GENESIS_HASH).unwrap(),
+            saturn.api.runtime_version(),
+            saturn.api.metadata(),
+            std::sync::Arc::new(RejectingRpcClient(rejection)),
+        )
+        .unwrap();
+
+        let call = saturn
+            .create_core(
+                String::from("core"),
+                500_000_000,
+                500_000_000,
+                &JsFeeAsset::tnkr(),
+            )
+            .unwrap();
+
+        futures::executor::block_on(async {
+            let payload = js_signer::generate_payload(
+                &call.api,
+                String::from(ALICE),
+                0,
+                call_data(&call),
+                None,
+                0,
+            )
+            .unwrap();
+
+            let extrinsic = call
+                .apply_signature(&payload, &js_signer::to_hex([0; 64]), Some("sr25519"))
+                .unwrap();
+
+            call.submit(extrinsic, Finality::InBlock, None, None)
+                .await
+                .err()
+                .unwrap()
+        })
+    }
+
+    #[test]
+    fn stale_nonce_submissions_are_retried() {
+        for rejection in [
+            PoolRejection {
+                code: 1010,
+                message: "Invalid Transaction",
+                data: "Transaction is outdated",
+            },
+            PoolRejection {
+                code: 1010,
+                message: "Invalid Transaction",
+                data: "Transaction will be valid in the future",
+            },
+            PoolRejection {
+                code: 1014,
+                message: "Priority is too low: (0 vs 0)",
+                data: "The transaction has too low priority to replace another transaction already in the pool.",
+            },
+        ] {
+            let error = rejected_submission(rejection);
+
+            assert_eq!(error.kind, ErrorKind::StaleNonce, "{:?}", error.details);
+            assert!(retries_with_fresh_nonce(true, None, &error));
+            assert!(!retries_with_fresh_nonce(true, Some(0), &error));
+            assert!(!retries_with_fresh_nonce(false, None, &error));
+        }
+    }
+
+    #[test]
+    fn other_rejected_submissions_are_not_retried() {
+        let error = rejected_submission(PoolRejection {
+            code: 1010,
+            message: "Invalid Transaction",
+            data: "Inability to pay some fees (e.g. account balance too low)",
+        });
+
+        assert_eq!(error.kind, ErrorKind::InvalidTransaction);
+        assert!(error.details.is_some());
+        assert!(!retries_with_fresh_nonce(true, None, &error));
+    }
+}
@@ -0,0 +1,34 @@
+use serde_json::value::RawValue;
+use subxt::{
+    error::RpcError,
+    rpc::{RpcClientT, RpcFuture, RpcSubscription},
+};
+
+/// `RpcClientT` for clients built with `Saturn::new_offline`, failing every request so nothing
+/// silently waits on a node that isn't there.
+pub struct OfflineRpcClient;
+
+impl RpcClientT for OfflineRpcClient {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        _params: Option<Box<RawValue>>,
+    ) -> RpcFuture<'a, Box<RawValue>> {
+        Box::pin(async move { Err(offline_error(method)) })
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        sub: &'a str,
+        _params: Option<Box<RawValue>>,
+        _unsub: &'a str,
+    ) -> RpcFuture<'a, RpcSubscription> {
+        Box::pin(async move { Err(offline_error(sub)) })
+    }
+}
+
+fn offline_error(method: &str) -> RpcError {
+    RpcError::ClientError(
+        format!("{} needs a connected client, this one is offline", method).into(),
+    )
+}
@@ -0,0 +1,188 @@
+use jsonrpsee_core::Error as JsonRpseeError;
+use subxt::{error::RpcError, Error as SubxtError, OnlineClient, PolkadotConfig};
+#[cfg(target_arch = "wasm32")]
+use {
+    crate::js_rpc::SendFuture,
+    serde_json::value::RawValue,
+    std::sync::{Arc, Mutex},
+    subxt::rpc::{RpcClient, RpcClientT, RpcFuture, RpcSubscription},
+};
+
+/// Connects an `OnlineClient` to `url` through a `ReconnectingRpcClient`, so the client and
+/// every `Call` built from it survive the WebSocket dropping.
+#[cfg(target_arch = "wasm32")]
+pub async fn online_client(url: &str) -> Result<OnlineClient<PolkadotConfig>, SubxtError> {
+    let rpc_client = ReconnectingRpcClient::connect(url.to_string()).await?;
+
+    OnlineClient::<PolkadotConfig>::from_rpc_client(Arc::new(rpc_client)).await
+}
+
+/// `ReconnectingRpcClient` is only `Send` on wasm32, see `SendFuture`, so native builds connect
+/// without reconnecting.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn online_client(url: &str) -> Result<OnlineClient<PolkadotConfig>, SubxtError> {
+    OnlineClient::<PolkadotConfig>::from_url(url).await
+}
+
+/// `RpcClientT` over a WebSocket connection that is re-established when a request or
+/// subscription fails because the connection dropped, retrying it once on the new connection.
+///
+/// Errors the node answered with, such as invalid transactions, are returned as is, and
+/// subscriptions that were open when the connection dropped still end.
+#[cfg(target_arch = "wasm32")]
+pub struct ReconnectingRpcClient {
+    url: String,
+    connection: Mutex<Connection>,
+}
+
+#[cfg(target_arch = "wasm32")]
+struct Connection {
+    client: RpcClient,
+    /// Bumped on every reconnect, so requests failing together only reconnect once.
+    generation: u64,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl ReconnectingRpcClient {
+    pub async fn connect(url: String) -> Result<ReconnectingRpcClient, SubxtError> {
+        let client = RpcClient::from_url(&url).await?;
+
+        Ok(ReconnectingRpcClient {
+            url,
+            connection: Mutex::new(Connection {
+                client,
+                generation: 0,
+            }),
+        })
+    }
+
+    fn current(&self) -> (RpcClient, u64) {
+        let connection = self.connection.lock().unwrap();
+        (connection.client.clone(), connection.generation)
+    }
+
+    /// Replaces the connection that failed at `generation`, unless another request already
+    /// did, and returns the one to retry on.
+    async fn reconnect(&self, generation: u64) -> Result<RpcClient, RpcError> {
+        let (client, current_generation) = self.current();
+        if current_generation != generation {
+            return Ok(client);
+        }
+
+        let client = RpcClient::from_url(&self.url)
+            .await
+            .map_err(|e| RpcError::ClientError(Box::new(e)))?;
+
+        let mut connection = self.connection.lock().unwrap();
+        if connection.generation == generation {
+            connection.client = client;
+            connection.generation += 1;
+        }
+
+        Ok(connection.client.clone())
+    }
+
+    async fn request(
+        &self,
+        method: &str,
+        params: Option<Box<RawValue>>,
+    ) -> Result<Box<RawValue>, RpcError> {
+        let (client, generation) = self.current();
+
+        match client.request_raw(method, params.clone()).await {
+            Err(error) if is_transport_error(&error) => {
+                self.reconnect(generation)
+                    .await?
+                    .request_raw(method, params)
+                    .await
+            }
+            result => result,
+        }
+    }
+
+    async fn subscribe(
+        &self,
+        sub: &str,
+        params: Option<Box<RawValue>>,
+        unsub: &str,
+    ) -> Result<RpcSubscription, RpcError> {
+        let (client, generation) = self.current();
+
+        match client.subscribe_raw(sub, params.clone(), unsub).await {
+            Err(error) if is_transport_error(&error) => {
+                self.reconnect(generation)
+                    .await?
+                    .subscribe_raw(sub, params, unsub)
+                    .await
+            }
+            result => result,
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl RpcClientT for ReconnectingRpcClient {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        params: Option<Box<RawValue>>,
+    ) -> RpcFuture<'a, Box<RawValue>> {
+        Box::pin(SendFuture(Box::pin(self.request(method, params))))
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        sub: &'a str,
+        params: Option<Box<RawValue>>,
+        unsub: &'a str,
+    ) -> RpcFuture<'a, RpcSubscription> {
+        Box::pin(SendFuture(Box::pin(self.subscribe(sub, params, unsub))))
+    }
+}
+
+/// Whether the request failed because the connection is gone rather than because of what was
+/// asked: a dropped subscription, or jsonrpsee's `Transport` and `RestartNeeded` errors, which
+/// subxt boxes into `ClientError`.
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+fn is_transport_error(error: &RpcError) -> bool {
+    match error {
+        RpcError::SubscriptionDropped => true,
+        RpcError::ClientError(error) => matches!(
+            error.downcast_ref::<JsonRpseeError>(),
+            Some(JsonRpseeError::Transport(_) | JsonRpseeError::RestartNeeded(_))
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transport_errors_are_recognised() {
+        assert!(is_transport_error(&RpcError::SubscriptionDropped));
+        assert!(is_transport_error(&RpcError::ClientError(Box::new(
+            JsonRpseeError::Transport(anyhow::anyhow!("connection closed"))
+        ))));
+        assert!(is_transport_error(&RpcError::ClientError(Box::new(
+            JsonRpseeError::RestartNeeded(String::from("connection closed"))
+        ))));
+    }
+
+    #[test]
+    fn other_errors_are_not_transport_errors() {
+        // Only the variant counts, not an error that reads like a transport error.
+        assert!(!is_transport_error(&RpcError::ClientError(Box::new(
+            JsonRpseeError::Custom(String::from(
+                "Networking or low-level protocol error; restart required"
+            ))
+        ))));
+        assert!(!is_transport_error(&RpcError::ClientError(Box::new(
+            JsonRpseeError::RequestTimeout
+        ))));
+        assert!(!is_transport_error(&RpcError::ClientError(Box::new(
+            std::io::Error::new(std::io::ErrorKind::Other, "restart required")
+        ))));
+    }
+}
@@ -0,0 +1,80 @@
+use subxt::utils::AccountId32;
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// SS58 address of `account` under network `prefix`, as substrate's `to_ss58check_with_version`.
+pub fn encode(account: &AccountId32, prefix: u16) -> String {
+    let mut bytes = match prefix {
+        0..=63 => vec![prefix as u8],
+        _ => vec![
+            ((prefix & 0b0000_0000_1111_1100) >> 2) as u8 | 0b0100_0000,
+            (prefix >> 8) as u8 | ((prefix & 0b0000_0000_0000_0011) << 6) as u8,
+        ],
+    };
+    bytes.extend(account.0);
+
+    let mut preimage = b"SS58PRE".to_vec();
+    preimage.extend(&bytes);
+    bytes.extend(&sp_core_hashing::blake2_512(&preimage)[..2]);
+
+    base58(&bytes)
+}
+
+fn base58(bytes: &[u8]) -> String {
+    // Little endian base 58 digits of `bytes` read as a big endian number.
+    let mut digits: Vec<u8> = Vec::new();
+    for byte in bytes {
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    // Leading zero bytes are kept as leading '1's.
+    let zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+
+    std::iter::repeat(b'1')
+        .take(zeros)
+        .chain(
+            digits
+                .iter()
+                .rev()
+                .map(|digit| BASE58_ALPHABET[*digit as usize]),
+        )
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Alice's public key, from the `//Alice` dev seed.
+    const ALICE: [u8; 32] = [
+        0xd4, 0x35, 0x93, 0xc7, 0x15, 0xfd, 0xd3, 0x1c, 0x61, 0x14, 0x1a, 0xbd, 0x04, 0xa9, 0x9f,
+        0xd6, 0x82, 0x2c, 0x85, 0x58, 0x85, 0x4c, 0xcd, 0xe3, 0x9a, 0x56, 0x84, 0xe7, 0xa5, 0x6d,
+        0xa2, 0x7d,
+    ];
+
+    #[test]
+    fn encodes_generic_substrate_address() {
+        assert_eq!(
+            encode(&AccountId32(ALICE), 42),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+    }
+
+    #[test]
+    fn encodes_two_byte_prefix() {
+        assert_eq!(
+            encode(&AccountId32(ALICE), 117),
+            "i52fucAbyb8FxttgXjALRAK2fKzv67kBBSpELCFDGXpv6TTzB"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{timeout, Finality};
+use wasm_bindgen::prelude::*;
+
+/// Options for `Call.sign_and_submit`, so new options don't change its signature. Every
+/// option is optional.
+///
+/// ```js
+/// const result = await call.sign_and_submit(
+///   address,
+///   signer,
+///   new SubmitOptions()
+///     .mortality_period(64)
+///     .finality(Finality.Finalized)
+///     .progress_callback(({ status }) => console.log(status)),
+/// );
+/// ```
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct SubmitOptions {
+    pub(crate) mortality_period: Option<u32>,
+    pub(crate) tip: Option<String>,
+    pub(crate) finality: Option<Finality>,
+    pub(crate) nonce: Option<u64>,
+    pub(crate) progress_callback: Option<js_sys::Function>,
+    pub(crate) retry_on_stale_nonce: bool,
+    pub(crate) abort_signal: Option<timeout::AbortSignal>,
+}
+
+#[wasm_bindgen]
+impl SubmitOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> SubmitOptions {
+        Self::default()
+    }
+
+    /// Makes the transaction only valid for `period` blocks after the current best block, so
+    /// it can't be replayed later. Without it, the client's `default_mortality` applies, and
+    /// the transaction is immortal if there is none.
+    #[wasm_bindgen]
+    pub fn mortality_period(mut self, period: u32) -> SubmitOptions {
+        self.mortality_period = Some(period);
+        self
+    }
+
+    /// Decimal amount paid to the block author for priority, defaulting to the client's
+    /// `default_tip`.
+    #[wasm_bindgen]
+    pub fn tip(mut self, tip: String) -> SubmitOptions {
+        self.tip = Some(tip);
+        self
+    }
+
+    /// Whether to resolve once the extrinsic is in a block, the default, or once that block is
+    /// finalized.
+    #[wasm_bindgen]
+    pub fn finality(mut self, finality: Finality) -> SubmitOptions {
+        self.finality = Some(finality);
+        self
+    }
+
+    /// Overrides the account's on-chain nonce, for callers submitting several calls before the
+    /// first is included, see `Saturn.account_next_nonce`.
+    #[wasm_bindgen]
+    pub fn nonce(mut self, nonce: u64) -> SubmitOptions {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Called with `{ status, block_hash? }` for each status update of the transaction, with
+    /// `status` one of `future`, `ready`, `broadcast`, `in_block`, `retracted`,
+    /// `finality_timeout`, `finalized`, `usurped`, `dropped` or `invalid`.
+    #[wasm_bindgen]
+    pub fn progress_callback(mut self, callback: js_sys::Function) -> SubmitOptions {
+        self.progress_callback = Some(callback);
+        self
+    }
+
+    /// Rebuilds a transaction the pool rejects with a `StaleNonce` error once, with the
+    /// account's next nonce, e.g. when another tab submitted first. That asks the signer for a
+    /// second signature, as the payload changes. A `nonce` set here is never replaced, so
+    /// there is no retry with one.
+    #[wasm_bindgen]
+    pub fn retry_on_stale_nonce(mut self) -> SubmitOptions {
+        self.retry_on_stale_nonce = true;
+        self
+    }
+
+    /// Stops waiting for the transaction and releases its status subscription once `signal`
+    /// is aborted, failing with an `Aborted` error. It doesn't cancel the transaction: once
+    /// submitted it may still be included.
+    #[wasm_bindgen]
+    pub fn abort_signal(mut self, signal: timeout::AbortSignal) -> SubmitOptions {
+        self.abort_signal = Some(signal);
+        self
+    }
+}
@@ -0,0 +1,35 @@
+use futures::future::{AbortHandle, Abortable};
+use std::future::Future;
+use wasm_bindgen::prelude::*;
+
+/// Handle to a background subscription. Calling `unsubscribe`, or freeing the handle,
+/// stops it.
+#[wasm_bindgen]
+pub struct Subscription {
+    handle: AbortHandle,
+}
+
+#[wasm_bindgen]
+impl Subscription {
+    #[wasm_bindgen]
+    pub fn unsubscribe(&self) {
+        self.handle.abort();
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// Runs `future` on the JS event loop until it completes or the returned handle stops it.
+pub fn spawn(future: impl Future<Output = ()> + 'static) -> Subscription {
+    let (handle, registration) = AbortHandle::new_pair();
+
+    wasm_bindgen_futures::spawn_local(async move {
+        let _ = Abortable::new(future, registration).await;
+    });
+
+    Subscription { handle }
+}
@@ -0,0 +1,116 @@
+use crate::SaturnError;
+use futures::future::{self, Either};
+use std::future::Future;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &js_sys::Function, timeout: u32) -> JsValue;
+
+    /// A DOM `AbortSignal`, as from `new AbortController().signal`.
+    pub type AbortSignal;
+
+    #[wasm_bindgen(method, getter)]
+    fn aborted(this: &AbortSignal) -> bool;
+
+    #[wasm_bindgen(method, js_name = addEventListener)]
+    fn add_event_listener(
+        this: &AbortSignal,
+        event: &str,
+        listener: &js_sys::Function,
+        options: &JsValue,
+    );
+
+    #[wasm_bindgen(method, js_name = removeEventListener)]
+    fn remove_event_listener(this: &AbortSignal, event: &str, listener: &js_sys::Function);
+}
+
+/// Resolves after `timeout_ms` on the JS event loop, since WASM has no `tokio::time`.
+async fn sleep(timeout_ms: u32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        set_timeout(&resolve, timeout_ms);
+    });
+
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+/// Races `future` against `timeout_ms`, failing with a `Timeout` error if the timer wins.
+/// Without a timeout it just awaits `future`.
+pub async fn with_timeout<F: Future>(
+    timeout_ms: Option<u32>,
+    what: &str,
+    future: F,
+) -> Result<F::Output, SaturnError> {
+    let Some(timeout_ms) = timeout_ms else {
+        return Ok(future.await);
+    };
+
+    let future = Box::pin(future);
+    let timer = Box::pin(sleep(timeout_ms));
+
+    match future::select(future, timer).await {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(_) => Err(SaturnError::timeout(format!(
+            "{} timed out after {}ms",
+            what, timeout_ms
+        ))),
+    }
+}
+
+/// Resolves once `signal` is aborted. Its listener is removed again once the future completes
+/// or is dropped, so racing many submissions against one long-lived signal doesn't pile up
+/// listeners on it.
+async fn aborted(signal: &AbortSignal) {
+    if signal.aborted() {
+        return;
+    }
+
+    let mut resolve_on_abort = None;
+    let promise = js_sys::Promise::new(&mut |resolve, _| resolve_on_abort = Some(resolve));
+
+    // The promise's executor runs synchronously, so the resolve function is always set here.
+    let Some(listener) = resolve_on_abort else {
+        return;
+    };
+
+    let options = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&options, &JsValue::from_str("once"), &JsValue::TRUE);
+    signal.add_event_listener("abort", &listener, &options);
+
+    let _listener = AbortListener { signal, listener };
+
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+/// An `abort` listener on a signal, removed when dropped.
+struct AbortListener<'a> {
+    signal: &'a AbortSignal,
+    listener: js_sys::Function,
+}
+
+impl Drop for AbortListener<'_> {
+    fn drop(&mut self) {
+        self.signal.remove_event_listener("abort", &self.listener);
+    }
+}
+
+/// Races `future` against `signal`, dropping it and failing with an `Aborted` error once the
+/// signal is aborted. Without a signal it just awaits `future`.
+pub async fn until_aborted<F: Future>(
+    signal: Option<&AbortSignal>,
+    what: &str,
+    future: F,
+) -> Result<F::Output, SaturnError> {
+    let Some(signal) = signal else {
+        return Ok(future.await);
+    };
+
+    let future = Box::pin(future);
+    let abort = Box::pin(aborted(signal));
+
+    match future::select(future, abort).await {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(_) => Err(SaturnError::aborted(format!("{} was aborted", what))),
+    }
+}
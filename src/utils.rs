@@ -17,8 +17,17 @@ extern "C" {
     pub fn log(s: &str);
 }
 
+/// Prepends the instance label, if any, so logs from multiple `Saturn` instances can be told apart.
+pub fn prefixed(label: Option<&str>, message: &str) -> String {
+    match label {
+        Some(label) => format!("[{}] {}", label, message),
+        None => message.to_string(),
+    }
+}
+
 #[macro_export]
 macro_rules! console_log {
+    (label = $label:expr; $($t:tt)*) => ($crate::utils::log(&$crate::utils::prefixed($label, &format_args!($($t)*).to_string())));
     ($($t:tt)*) => ($crate::utils::log(&format_args!($($t)*).to_string()))
 }
 